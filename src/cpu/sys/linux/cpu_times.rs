@@ -89,7 +89,7 @@ pub fn get_each_cputimes() -> Result<Vec<CpuTimes>, Error> {
             continue;
         }
         // If line does not start with cpu, stop
-        if line[..3].as_bytes() != b"cpu" {
+        if &line.as_bytes()[..3] != b"cpu" {
             break;
         }
         // Split whitespaces and get an Array of values
@@ -0,0 +1,258 @@
+use crate::disks::Disks;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Regression used to fit the usage history.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum RegressionMethod {
+    /// Ordinary least squares, cheap but sensitive to outliers (eg: a temporary file)
+    Linear,
+    /// Theil-Sen estimator (median of the pairwise slopes), robust to outliers
+    Robust,
+}
+
+/// Struct containing the forecast of a mount point.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskForecast {
+    pub mount_point: String,
+    /// Number of samples used for the forecast
+    pub samples: usize,
+    /// Positive when the usage grows. Value is in bytes/hour
+    pub growth_rate: f64,
+    /// None if the usage is not growing. Value is in seconds
+    pub time_to_full: Option<f64>,
+    /// Coefficient of determination (R²) of the fit, between 0 and 1
+    pub confidence: f64,
+    /// Positive when the inode usage grows. Value is in inodes/hour
+    pub inode_growth_rate: f64,
+    /// None if the inode usage is not growing. Value is in seconds
+    pub time_to_inode_exhaustion: Option<f64>,
+    /// Coefficient of determination (R²) of the inode fit, between 0 and 1
+    pub inode_confidence: f64,
+}
+
+// Maximum number of samples kept per mount point, the robust fit
+// computes every pairwise slope so its cost is quadratic.
+const MAX_SAMPLES: usize = 512;
+
+#[derive(Debug, Clone)]
+struct Sample {
+    /// Seconds since UNIX_EPOCH
+    time: f64,
+    available: f64,
+    available_inodes: f64,
+}
+
+/// Forecast when the disks will be full from a history of [Disks] samples.
+///
+/// ```ignore
+/// use sys_metrics::disks::*;
+/// use std::time::{Duration, SystemTime};
+///
+/// let mut forecaster = DiskForecaster::new(Duration::from_secs(6 * 3600));
+/// // Every minute or so
+/// forecaster.add_samples(SystemTime::now(), &get_partitions_physical().unwrap());
+///
+/// for forecast in forecaster.forecasts() {
+///     if forecast.time_to_full.is_some_and(|ttf| ttf < 4.0 * 3600.0) {
+///         println!("{} will be full in less than 4 hours", forecast.mount_point);
+///     }
+/// }
+/// ```
+///
+/// [Disks]: ../disks/struct.Disks.html
+#[derive(Debug, Clone)]
+pub struct DiskForecaster {
+    window: Duration,
+    method: RegressionMethod,
+    history: HashMap<String, VecDeque<Sample>>,
+}
+
+/// Fit `y = slope * x + intercept`, return (slope, intercept).
+fn fit(method: RegressionMethod, xs: &[f64], ys: &[f64]) -> (f64, f64) {
+    let n = xs.len() as f64;
+    match method {
+        RegressionMethod::Linear => {
+            let mean_x = xs.iter().sum::<f64>() / n;
+            let mean_y = ys.iter().sum::<f64>() / n;
+            let (mut cov, mut var) = (0.0, 0.0);
+            for (x, y) in xs.iter().zip(ys) {
+                cov += (x - mean_x) * (y - mean_y);
+                var += (x - mean_x) * (x - mean_x);
+            }
+            let slope = if var == 0.0 { 0.0 } else { cov / var };
+            (slope, mean_y - slope * mean_x)
+        }
+        RegressionMethod::Robust => {
+            let mut slopes = Vec::with_capacity(xs.len() * (xs.len() - 1) / 2);
+            for i in 0..xs.len() {
+                for j in i + 1..xs.len() {
+                    if xs[j] != xs[i] {
+                        slopes.push((ys[j] - ys[i]) / (xs[j] - xs[i]));
+                    }
+                }
+            }
+            let slope = median(&mut slopes);
+            let mut intercepts: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| y - slope * x).collect();
+            (slope, median(&mut intercepts))
+        }
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = values.len() / 2;
+    if mid * 2 == values.len() {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Coefficient of determination of the fit, 1 for a perfect (or flat) series.
+fn r_squared(xs: &[f64], ys: &[f64], slope: f64, intercept: f64) -> f64 {
+    let mean_y = ys.iter().sum::<f64>() / ys.len() as f64;
+    let (mut ss_res, mut ss_tot) = (0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        let predicted = slope * x + intercept;
+        ss_res += (y - predicted) * (y - predicted);
+        ss_tot += (y - mean_y) * (y - mean_y);
+    }
+    if ss_tot == 0.0 {
+        return 1.0;
+    }
+    (1.0 - ss_res / ss_tot).clamp(0.0, 1.0)
+}
+
+/// Return (growth per hour, time to exhaustion, confidence) of a decreasing availability.
+fn forecast_series(method: RegressionMethod, xs: &[f64], ys: &[f64]) -> (f64, Option<f64>, f64) {
+    // Work relative to the first sample to keep the precision with big timestamps
+    let origin = xs[0];
+    let xs: Vec<f64> = xs.iter().map(|x| x - origin).collect();
+    let (slope, intercept) = fit(method, &xs, ys);
+    let confidence = r_squared(&xs, ys, slope, intercept);
+
+    // The availability decreases when the usage grows
+    let growth = -slope;
+    let remaining = ys[ys.len() - 1];
+    let time_to_exhaustion = if growth > 0.0 {
+        Some((remaining / growth).max(0.0))
+    } else {
+        None
+    };
+
+    (growth * 3600.0, time_to_exhaustion, confidence)
+}
+
+impl DiskForecaster {
+    /// Create a forecaster keeping the samples of the last `window`.
+    ///
+    /// It uses the [RegressionMethod::Robust] by default.
+    ///
+    /// At most 512 samples are kept per mount point: past that, every other sample
+    /// is dropped, so the history still covers the whole window with a lower resolution.
+    ///
+    /// [RegressionMethod::Robust]: ../disks/enum.RegressionMethod.html#variant.Robust
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            method: RegressionMethod::Robust,
+            history: HashMap::new(),
+        }
+    }
+
+    /// Change the regression used to fit the history.
+    pub fn method(mut self, method: RegressionMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Record the [Disks] gathered at `time`.
+    ///
+    /// Entries with an error are ignored, samples older than the window are dropped.
+    ///
+    /// [Disks]: ../disks/struct.Disks.html
+    pub fn add_samples(&mut self, time: SystemTime, disks: &[Disks]) {
+        let time = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+
+        for disk in disks.iter().filter(|disk| disk.error.is_none()) {
+            self.history
+                .entry(disk.mount_point.clone())
+                .or_default()
+                .push_back(Sample {
+                    time,
+                    available: disk.usage.available as f64,
+                    available_inodes: disk.usage.available_inodes as f64,
+                });
+        }
+
+        let oldest = time - self.window.as_secs_f64();
+        for samples in self.history.values_mut() {
+            while samples.front().is_some_and(|sample| sample.time < oldest) {
+                samples.pop_front();
+            }
+            if samples.len() > MAX_SAMPLES {
+                // Keep the even samples, and always the latest one
+                let last = samples.len() - 1;
+                let mut idx = 0;
+                samples.retain(|_| {
+                    let keep = idx % 2 == 0 || idx == last;
+                    idx += 1;
+                    keep
+                });
+            }
+        }
+        self.history.retain(|_, samples| !samples.is_empty());
+    }
+
+    /// Return the [DiskForecast] of a mount point.
+    ///
+    /// Return None if there's less than 2 samples for it.
+    ///
+    /// [DiskForecast]: ../disks/struct.DiskForecast.html
+    pub fn forecast(&self, mount_point: &str) -> Option<DiskForecast> {
+        let samples = self.history.get(mount_point)?;
+        if samples.len() < 2 {
+            return None;
+        }
+
+        let xs: Vec<f64> = samples.iter().map(|s| s.time).collect();
+        let space: Vec<f64> = samples.iter().map(|s| s.available).collect();
+        let inodes: Vec<f64> = samples.iter().map(|s| s.available_inodes).collect();
+        let (growth_rate, time_to_full, confidence) = forecast_series(self.method, &xs, &space);
+        let (inode_growth_rate, time_to_inode_exhaustion, inode_confidence) =
+            forecast_series(self.method, &xs, &inodes);
+
+        Some(DiskForecast {
+            mount_point: mount_point.to_owned(),
+            samples: samples.len(),
+            growth_rate,
+            time_to_full,
+            confidence,
+            inode_growth_rate,
+            time_to_inode_exhaustion,
+            inode_confidence,
+        })
+    }
+
+    /// Return the [DiskForecast] of every mount point with enough samples.
+    ///
+    /// [DiskForecast]: ../disks/struct.DiskForecast.html
+    pub fn forecasts(&self) -> Vec<DiskForecast> {
+        let mut forecasts: Vec<DiskForecast> = self
+            .history
+            .keys()
+            .filter_map(|mount_point| self.forecast(mount_point))
+            .collect();
+        forecasts.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        forecasts
+    }
+}
@@ -1,10 +1,16 @@
 use libc::statvfs;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::ffi::CString;
-use std::io::Error;
+use std::io::{Error, ErrorKind};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
 
+mod forecast;
 mod sys;
 
+pub use forecast::*;
 pub use sys::*;
 
 /// Struct containing a disk' information.
@@ -12,10 +18,55 @@ pub use sys::*;
 pub struct Disks {
     pub name: String,
     pub mount_point: String,
+    pub fstype: String,
+    pub kind: FilesystemKind,
     /// Value is in MB
     pub total_space: u64,
     /// Value is in MB
     pub avail_space: u64,
+    /// Detailed usage (in bytes) of the filesystem
+    pub usage: DiskUsage,
+    /// Set if the usage could not be gathered (timeout, permission denied, ...),
+    /// in which case the space fields are left to 0
+    pub error: Option<String>,
+}
+
+/// Kind of filesystem a mount is made of.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum FilesystemKind {
+    /// Filesystem stored on a real block device (ext4, xfs, btrfs, zfs, ...)
+    Physical,
+    /// Block filesystem stored on a virtual device (loop, ram, zram)
+    Virtual,
+    /// Remote filesystem (nfs, cifs, ceph, ...)
+    Network,
+    /// Userspace filesystem (`fuse`, `fuse.*`)
+    Fuse,
+    /// Union filesystem (overlay, aufs, ...)
+    Overlay,
+    /// Memory backed filesystem (tmpfs, ramfs, devtmpfs)
+    Tmpfs,
+    /// Kernel interface or any other filesystem without storage (proc, sysfs, cgroup, ...)
+    #[default]
+    Pseudo,
+}
+
+/// Classify a filesystem based on its name only.
+///
+/// Return None if the name alone is not enough to tell.
+pub(crate) fn filesystem_kind_from_name(fstype: &str) -> Option<FilesystemKind> {
+    match fstype {
+        "nfs" | "nfs4" | "cifs" | "smb3" | "smbfs" | "ncpfs" | "ceph" | "glusterfs" | "9p"
+        | "afs" | "lustre" | "gpfs" | "beegfs" | "webdav" => Some(FilesystemKind::Network),
+        "fuse" => Some(FilesystemKind::Fuse),
+        _ if fstype.starts_with("fuse.") => Some(FilesystemKind::Fuse),
+        "overlay" | "aufs" | "unionfs" => Some(FilesystemKind::Overlay),
+        "tmpfs" | "ramfs" | "devtmpfs" => Some(FilesystemKind::Tmpfs),
+        // zfs does not require a block device from the kernel point of view (nodev)
+        // but its pools are nonetheless stored on disks.
+        "zfs" => Some(FilesystemKind::Physical),
+        _ => None,
+    }
 }
 
 /// Struct containing a disk_io (bytes read/wrtn) information.
@@ -27,11 +78,545 @@ pub struct IoBlock {
     pub write_count: u64,
     pub write_bytes: u64,
     pub busy_time: u64,
+    /// True for attached loop devices (image-backed, eg: snaps) and their partitions,
+    /// always false on macOS
+    pub is_loop: bool,
+}
+
+/// Struct containing a loop device's information (from `/sys/block/loop*/loop/`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoopDevice {
+    pub name: String,
+    /// File the device is backed by (may end with ` (deleted)`)
+    pub backing_file: String,
+    /// Value is in bytes
+    pub offset: u64,
+    /// Value is in bytes, 0 means no limit
+    pub size_limit: u64,
+    /// Value is in bytes
+    pub size: u64,
+    /// The device is detached on last close
+    pub autoclear: bool,
+    pub partscan: bool,
+    pub read_only: bool,
+    pub direct_io: bool,
+}
+
+/// Struct containing a mount's information (from `/proc/self/mountinfo`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Mount {
+    pub mount_id: u32,
+    pub parent_id: u32,
+    pub major: u32,
+    pub minor: u32,
+    /// Root of the mount within the filesystem (differ from `/` for bind mounts)
+    pub root: String,
+    pub mount_point: String,
+    /// Per-mount options (`rw`, `nosuid`, `relatime`, ...)
+    pub mount_options: Vec<String>,
+    /// Optional fields such as the propagation (`shared:X`, `master:X`, ...)
+    pub optional_fields: Vec<String>,
+    pub fstype: String,
+    pub source: String,
+    /// Per-superblock options
+    pub super_options: Vec<String>,
+}
+
+impl Mount {
+    /// The mount is read-only, either per-mount or because its superblock is
+    /// (eg: ext4 remounted read-only after an error).
+    pub fn is_read_only(&self) -> bool {
+        self.mount_options.iter().any(|opt| opt == "ro")
+            || self.super_options.iter().any(|opt| opt == "ro")
+    }
+}
+
+/// Struct containing a block device's information (from `/sys/block`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlockDevice {
+    pub name: String,
+    pub major: u32,
+    pub minor: u32,
+    /// Value is in bytes
+    pub size: u64,
+    pub logical_sector_size: u64,
+    pub physical_sector_size: u64,
+    pub rotational: bool,
+    pub removable: bool,
+    pub read_only: bool,
+    pub model: Option<String>,
+    pub vendor: Option<String>,
+    pub serial: Option<String>,
+    pub wwn: Option<String>,
+    /// The active IO scheduler (the one between brackets in `queue/scheduler`)
+    pub scheduler: Option<String>,
+    /// `device/queue_depth` for SCSI devices, `queue/nr_requests` otherwise
+    pub queue_depth: Option<u64>,
+    pub partitions: Vec<BlockPartition>,
+    /// Devices built on top of this one (dm, md, ...)
+    pub holders: Vec<String>,
+    /// Devices this one is built on (for dm, md, ...)
+    pub slaves: Vec<String>,
+}
+
+/// Struct containing a partition of a [BlockDevice].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlockPartition {
+    pub name: String,
+    pub major: u32,
+    pub minor: u32,
+    pub number: u32,
+    /// Value is in bytes
+    pub start: u64,
+    /// Value is in bytes
+    pub size: u64,
+    pub read_only: bool,
+    /// Devices built on top of this partition (dm, md, ...)
+    pub holders: Vec<String>,
+}
+
+/// Struct containing the usage of a filesystem (from `statvfs`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskUsage {
+    /// Value is in bytes
+    pub total: u64,
+    /// Value is in bytes
+    pub used: u64,
+    /// Free space, including the blocks reserved for root. Value is in bytes
+    pub free: u64,
+    /// Free space available to unprivileged users. Value is in bytes
+    pub available: u64,
+    pub total_inodes: u64,
+    pub free_inodes: u64,
+    /// Free inodes available to unprivileged users
+    pub available_inodes: u64,
+    /// Preferred IO block size. Value is in bytes
+    pub block_size: u64,
+    /// Fundamental block size (unit of the space counters). Value is in bytes
+    pub fragment_size: u64,
+    pub max_filename_len: u64,
+    pub read_only: bool,
+    pub nosuid: bool,
+    /// Always false on macOS as `statvfs` does not report it
+    pub noexec: bool,
+}
+
+impl DiskUsage {
+    /// Return the space reserved for root (in bytes)
+    pub fn reserved(&self) -> u64 {
+        self.free.saturating_sub(self.available)
+    }
+
+    /// Return the number of inodes in use
+    pub fn used_inodes(&self) -> u64 {
+        self.total_inodes.saturating_sub(self.free_inodes)
+    }
+}
+
+/// Kind of a device-mapper device, guessed from its uuid prefix.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub enum DeviceMapperKind {
+    /// LVM logical volume (uuid prefixed by `LVM-`)
+    Lvm,
+    /// dm-crypt/LUKS mapping (uuid prefixed by `CRYPT-`)
+    Crypt,
+    /// Multipath device (uuid prefixed by `mpath-`)
+    Multipath,
+    /// Any other target
+    Other,
+}
+
+/// Struct containing a device-mapper's information (from `/sys/block/dm-*/dm/`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceMapper {
+    /// Kernel name (eg: `dm-0`)
+    pub device_name: String,
+    /// Mapping name (eg: `vg0-root` or `luks-<uuid>`)
+    pub name: String,
+    pub uuid: String,
+    pub kind: DeviceMapperKind,
+    /// Only set for LVM volumes
+    pub vg_name: Option<String>,
+    /// Only set for LVM volumes
+    pub lv_name: Option<String>,
+}
+
+/// Struct linking a mount to the block devices it's backed by.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MountDevice {
+    pub mount_point: String,
+    pub major: u32,
+    pub minor: u32,
+    /// Kernel name of the device (eg: `sda1`, `dm-0`, `md0`)
+    pub device_name: Option<String>,
+    /// Disk holding the partition, if `device_name` is one
+    pub parent_disk: Option<String>,
+    /// Device-mapper layers crossed from the mount down to the disks
+    pub device_mappers: Vec<DeviceMapper>,
+    /// md arrays crossed from the mount down to the disks
+    pub md_arrays: Vec<String>,
+    /// Whole disks backing the mount (names as in `/proc/diskstats`)
+    pub disks: Vec<String>,
+}
+
+impl MountDevice {
+    /// Return the [IoBlock] of the disks backing the mount.
+    ///
+    /// [IoBlock]: ../disks/struct.IoBlock.html
+    pub fn ioblocks<'a>(&self, ioblocks: &'a [IoBlock]) -> Vec<&'a IoBlock> {
+        ioblocks
+            .iter()
+            .filter(|io| self.disks.contains(&io.device_name))
+            .collect()
+    }
+}
+
+/// Struct containing a software RAID (md) array's information.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MdArray {
+    pub name: String,
+    /// RAID level (eg: `raid1`), empty for inactive arrays
+    pub level: String,
+    /// `active`/`inactive` from mdstat, or the `array_state` from sysfs if available
+    pub state: String,
+    pub read_only: bool,
+    /// Value is in bytes
+    pub size: u64,
+    /// Number of devices the array should have
+    pub raid_disks: u32,
+    /// Number of devices currently in sync
+    pub active_disks: u32,
+    /// Number of missing devices
+    pub degraded: u32,
+    pub members: Vec<MdMember>,
+    /// Ongoing (or pending) resync/recovery/check/reshape
+    pub sync: Option<MdSync>,
+}
+
+/// Struct containing a member of a [MdArray].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MdMember {
+    pub name: String,
+    /// Descriptor index of the device, as shown in mdstat (`sda1[0]`)
+    pub descriptor: u32,
+    /// Role of the device in the array, None for spares and failed devices
+    pub slot: Option<u32>,
+    pub faulty: bool,
+    pub spare: bool,
+    pub write_mostly: bool,
+    pub replacement: bool,
+}
+
+/// Struct containing the progress of a [MdArray] synchronization.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MdSync {
+    /// `resync`, `recovery`, `check`, `repair` or `reshape`
+    pub action: String,
+    /// True when the action is `DELAYED` or `PENDING`
+    pub pending: bool,
+    /// Value is in percent
+    pub progress: f64,
+    /// Value is in KB (1024 bytes blocks)
+    pub done: u64,
+    /// Value is in KB (1024 bytes blocks)
+    pub total: u64,
+    /// Value is in KB/s
+    pub speed: u64,
+    /// Estimated time to completion, value is in seconds
+    pub finish: Option<f64>,
+}
+
+/// Struct containing the statistics of a NFS mount (from `/proc/self/mountstats`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NfsMountStats {
+    pub server: String,
+    pub export: String,
+    pub mount_point: String,
+    pub fstype: String,
+    /// NFS version (the `vers=` mount option)
+    pub version: String,
+    pub options: Vec<String>,
+    /// Time since the mount, value is in seconds
+    pub age: u64,
+    pub bytes: NfsByteStats,
+    pub transport: NfsTransportStats,
+    pub operations: Vec<NfsOpStats>,
+}
+
+/// Struct containing the bytes counters of a NFS mount.
+///
+/// All values are in bytes, except the pages counters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NfsByteStats {
+    pub normal_read: u64,
+    pub normal_write: u64,
+    pub direct_read: u64,
+    pub direct_write: u64,
+    pub server_read: u64,
+    pub server_write: u64,
+    pub read_pages: u64,
+    pub write_pages: u64,
+}
+
+/// Struct containing the RPC transport counters of a NFS mount.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NfsTransportStats {
+    /// `tcp`, `udp` or `rdma`
+    pub protocol: String,
+    pub sends: u64,
+    pub recvs: u64,
+    pub bad_xids: u64,
+}
+
+/// Struct containing the counters of a NFS operation (READ, WRITE, GETATTR, ...).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NfsOpStats {
+    pub name: String,
+    pub ops: u64,
+    pub transmissions: u64,
+    pub major_timeouts: u64,
+    pub bytes_sent: u64,
+    pub bytes_recv: u64,
+    /// Cumulative time spent queued, value is in ms
+    pub queue_time: u64,
+    /// Cumulative round trip time, value is in ms
+    pub rtt: u64,
+    /// Cumulative execution time (queue + rtt + client side), value is in ms
+    pub execute_time: u64,
+    /// Only reported by recent kernels
+    pub errors: u64,
+}
+
+impl NfsOpStats {
+    /// Return the number of retransmissions
+    pub fn retransmissions(&self) -> u64 {
+        self.transmissions.saturating_sub(self.ops)
+    }
+}
+
+/// Struct containing the average latency of a NFS operation between two samples.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NfsOpLatency {
+    pub name: String,
+    /// Number of operations between the two samples
+    pub ops: u64,
+    pub retransmissions: u64,
+    /// Value is in ms
+    pub avg_queue_time: f64,
+    /// Value is in ms
+    pub avg_rtt: f64,
+    /// Value is in ms
+    pub avg_execute_time: f64,
+}
+
+impl NfsMountStats {
+    /// Return the RPC retransmissions of all the operations
+    pub fn retransmissions(&self) -> u64 {
+        self.operations.iter().map(|op| op.retransmissions()).sum()
+    }
+
+    /// Compute the per-op average latency between `prev` and this sample.
+    ///
+    /// Operations without any call in between are skipped.
+    pub fn latency_since(&self, prev: &NfsMountStats) -> Vec<NfsOpLatency> {
+        self.operations
+            .iter()
+            .filter_map(|curr| {
+                let old = prev.operations.iter().find(|op| op.name == curr.name);
+                let delta = |f: fn(&NfsOpStats) -> u64| f(curr).saturating_sub(old.map_or(0, f));
+                let ops = delta(|op| op.ops);
+                if ops == 0 {
+                    return None;
+                }
+
+                Some(NfsOpLatency {
+                    name: curr.name.clone(),
+                    ops,
+                    retransmissions: delta(|op| op.retransmissions()),
+                    avg_queue_time: delta(|op| op.queue_time) as f64 / ops as f64,
+                    avg_rtt: delta(|op| op.rtt) as f64 / ops as f64,
+                    avg_execute_time: delta(|op| op.execute_time) as f64 / ops as f64,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Struct containing the health information of a filesystem.
+///
+/// Only the member matching the fstype (`ext4`, `xfs` or `btrfs`) is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FilesystemHealth {
+    pub mount_point: String,
+    pub fstype: String,
+    /// Kernel name of the device (eg: `sda1`)
+    pub device: Option<String>,
+    /// Mounted read-only, either per-mount or per-superblock
+    pub read_only: bool,
+    /// The superblock is read-only while the mount is not, which is what ext4
+    /// does on errors with `errors=remount-ro`
+    pub remounted_read_only: bool,
+    /// Behavior on errors (the `errors=` option), if any
+    pub errors_behavior: Option<String>,
+    pub ext4: Option<Ext4Health>,
+    pub xfs: Option<XfsHealth>,
+    pub btrfs: Option<BtrfsHealth>,
+}
+
+impl FilesystemHealth {
+    /// Return true if the filesystem reported any error
+    pub fn has_errors(&self) -> bool {
+        self.remounted_read_only
+            || self.ext4.as_ref().is_some_and(|ext4| ext4.errors_count > 0)
+            || self.xfs.as_ref().is_some_and(|xfs| xfs.shutdown)
+            || self
+                .btrfs
+                .as_ref()
+                .is_some_and(|btrfs| btrfs.devices.iter().any(|dev| dev.total_errors() > 0))
+    }
+}
+
+/// Struct containing the ext4 counters (from `/sys/fs/ext4/<dev>/`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ext4Health {
+    pub errors_count: u64,
+    pub warning_count: u64,
+    pub msg_count: u64,
+    /// Unix timestamp, None if no error ever happened
+    pub first_error_time: Option<u64>,
+    /// Kernel function which reported the first error
+    pub first_error_func: Option<String>,
+    /// Unix timestamp, None if no error ever happened
+    pub last_error_time: Option<u64>,
+    /// Kernel function which reported the last error
+    pub last_error_func: Option<String>,
+    /// Value is in KB
+    pub lifetime_write_kbytes: u64,
+    pub delayed_allocation_blocks: u64,
+}
+
+/// Struct containing the xfs information (from `/sys/fs/xfs/<dev>/`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct XfsHealth {
+    /// Content of `stats/stats`, each line being `name values...`
+    pub stats: HashMap<String, Vec<u64>>,
+    /// The filesystem was shut down after an error (every access fails with EIO)
+    pub shutdown: bool,
+    pub fail_at_unmount: bool,
+    pub error_config: Vec<XfsErrorConfig>,
+}
+
+/// Struct containing how xfs handles an error class (from `/sys/fs/xfs/<dev>/error/`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct XfsErrorConfig {
+    /// Subsystem, eg: `metadata`
+    pub class: String,
+    /// Error, eg: `EIO`, `ENOSPC`, `ENODEV` or `default`
+    pub error: String,
+    /// -1 means retry forever
+    pub max_retries: i64,
+    /// Value is in seconds, -1 means retry forever
+    pub retry_timeout_seconds: i64,
+}
+
+/// Struct containing the btrfs information (from `/sys/fs/btrfs/<uuid>/`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BtrfsHealth {
+    pub uuid: String,
+    pub label: Option<String>,
+    pub devices: Vec<BtrfsDeviceErrors>,
+    pub allocation: Vec<BtrfsAllocation>,
+    /// Ongoing exclusive operation (`none`, `balance`, `resize`, ...), needs a 5.10+ kernel
+    pub exclusive_operation: Option<String>,
+}
+
+/// Struct containing the error counters of a btrfs device (needs a 5.14+ kernel).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BtrfsDeviceErrors {
+    pub devid: u64,
+    pub write_errs: u64,
+    pub read_errs: u64,
+    pub flush_errs: u64,
+    pub corruption_errs: u64,
+    pub generation_errs: u64,
+}
+
+impl BtrfsDeviceErrors {
+    /// Return the sum of all the error counters
+    pub fn total_errors(&self) -> u64 {
+        self.write_errs
+            + self.read_errs
+            + self.flush_errs
+            + self.corruption_errs
+            + self.generation_errs
+    }
+}
+
+/// Struct containing the allocation of a btrfs block group type for a profile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BtrfsAllocation {
+    /// `data`, `metadata` or `system`
+    pub block_group: String,
+    /// `single`, `dup`, `raid1`, ...
+    pub profile: String,
+    /// Value is in bytes
+    pub total_bytes: u64,
+    /// Value is in bytes
+    pub used_bytes: u64,
+}
+
+/// Struct containing an entry of `/etc/fstab`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FstabEntry {
+    /// The device as written (eg: `UUID=...`, `LABEL=...`, `/dev/sda1`, `server:/export`)
+    pub spec: String,
+    /// The device `spec` resolves to (through `/dev/disk/by-*`), if it's a local one
+    pub device: Option<String>,
+    pub mount_point: String,
+    pub fstype: String,
+    pub options: Vec<String>,
+    pub dump: u32,
+    pub pass: u32,
+}
+
+impl FstabEntry {
+    /// Return true if the entry is expected to be mounted at boot
+    /// (not swap, not `noauto`).
+    pub fn is_automount(&self) -> bool {
+        self.fstype != "swap"
+            && self.mount_point != "none"
+            && !self.options.iter().any(|opt| opt == "noauto")
+    }
+}
+
+/// Struct describing a mount whose options differ from its fstab entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FstabMismatch {
+    pub entry: FstabEntry,
+    pub mount: Mount,
+    /// Options of the entry not found on the mount
+    pub missing_options: Vec<String>,
+    /// The mount is read-only while the entry is not, or the other way around
+    pub read_only_mismatch: bool,
+    /// The mount's source is not the device the entry resolves to
+    pub device_mismatch: bool,
+}
+
+/// Struct containing the differences between `/etc/fstab` and the live mounts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FstabReport {
+    /// Entries which should be mounted but are not
+    pub not_mounted: Vec<FstabEntry>,
+    /// Physical and network mounts without an fstab entry
+    pub not_in_fstab: Vec<Mount>,
+    pub mismatches: Vec<FstabMismatch>,
 }
 
-/// Return the (total, free) space of a Disk from it's path (mount_point).
+/// Return the [DiskUsage] of a Disk from it's path (mount_point).
+///
+/// [DiskUsage]: ../disks/struct.DiskUsage.html
 #[allow(clippy::unnecessary_cast)]
-pub fn disk_usage<P>(path: P) -> Result<(u64, u64), Error>
+pub fn disk_usage<P>(path: P) -> Result<DiskUsage, Error>
 where
     P: AsRef<[u8]>,
 {
@@ -44,16 +629,323 @@ where
     let statvfs = unsafe { statvfs.assume_init() };
     // The cast here is needed for macOS. As it doesn't hurt on Linux,
     // don't do some cfg(target = XXX) machinery to workaround clippy.
-    let total = statvfs.f_blocks as u64 * statvfs.f_frsize as u64;
-    let free = statvfs.f_bavail as u64 * statvfs.f_frsize as u64;
+    let frsize = statvfs.f_frsize as u64;
+    let total = statvfs.f_blocks as u64 * frsize;
+    let free = statvfs.f_bfree as u64 * frsize;
+    let flags = statvfs.f_flag as libc::c_ulong;
+
+    Ok(DiskUsage {
+        total,
+        used: total.saturating_sub(free),
+        free,
+        available: statvfs.f_bavail as u64 * frsize,
+        total_inodes: statvfs.f_files as u64,
+        free_inodes: statvfs.f_ffree as u64,
+        available_inodes: statvfs.f_favail as u64,
+        block_size: statvfs.f_bsize as u64,
+        fragment_size: frsize,
+        max_filename_len: statvfs.f_namemax as u64,
+        read_only: flags & libc::ST_RDONLY != 0,
+        nosuid: flags & libc::ST_NOSUID != 0,
+        #[cfg(target_os = "linux")]
+        noexec: flags & libc::ST_NOEXEC != 0,
+        #[cfg(not(target_os = "linux"))]
+        noexec: false,
+    })
+}
 
-    Ok((total, free))
+lazy_static::lazy_static! {
+    /// Mounts for which a statvfs is still pending in a background thread.
+    static ref PENDING_PROBES: Mutex<HashSet<Vec<u8>>> = Mutex::new(HashSet::new());
+}
+
+/// Return the [DiskUsage] of a Disk from it's path (mount_point), waiting at most `timeout`.
+///
+/// The `statvfs` call is made from another thread, so a stale mount (eg: NFS/CIFS
+/// whose server is gone) returns an Error of kind `TimedOut` instead of blocking forever.
+/// The thread stays blocked until the kernel gives up. While it is, later calls for the
+/// same path fail immediately with `TimedOut` instead of spawning another one.
+///
+/// [DiskUsage]: ../disks/struct.DiskUsage.html
+pub fn disk_usage_with_timeout<P>(path: P, timeout: Duration) -> Result<DiskUsage, Error>
+where
+    P: AsRef<[u8]>,
+{
+    let path = path.as_ref().to_vec();
+    let timed_out = || Error::new(ErrorKind::TimedOut, "statvfs timed out");
+
+    if let Ok(mut pending) = PENDING_PROBES.lock() {
+        if !pending.insert(path.clone()) {
+            return Err(timed_out());
+        }
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let probe = path.clone();
+    let spawned = thread::Builder::new()
+        .name("sys_metrics-statvfs".to_owned())
+        .spawn(move || {
+            let usage = disk_usage(&probe);
+            if let Ok(mut pending) = PENDING_PROBES.lock() {
+                pending.remove(&probe);
+            }
+            // The receiver may be gone if we timed out, nothing to do then.
+            let _ = tx.send(usage);
+        });
+    if let Err(err) = spawned {
+        if let Ok(mut pending) = PENDING_PROBES.lock() {
+            pending.remove(&path);
+        }
+        return Err(err);
+    }
+
+    match rx.recv_timeout(timeout) {
+        Ok(usage) => usage,
+        Err(_) => Err(timed_out()),
+    }
+}
+
+/// Build a [Disks] entry, a failure to get the usage is reported in its `error` field.
+///
+/// With a timeout, the usage of network and FUSE mounts (the ones which can hang)
+/// is gathered with [disk_usage_with_timeout].
+pub(crate) fn build_disks(
+    name: String,
+    mount_point: String,
+    fstype: String,
+    kind: FilesystemKind,
+    timeout: Option<Duration>,
+) -> Disks {
+    let usage = match timeout {
+        Some(timeout) if matches!(kind, FilesystemKind::Network | FilesystemKind::Fuse) => {
+            disk_usage_with_timeout(mount_point.as_bytes(), timeout)
+        }
+        _ => disk_usage(mount_point.as_bytes()),
+    };
+
+    let mut disks = Disks {
+        name,
+        mount_point,
+        fstype,
+        kind,
+        ..Default::default()
+    };
+    match usage {
+        Ok(usage) => {
+            disks.total_space = usage.total / (1024 * 1024);
+            disks.avail_space = usage.available / (1024 * 1024);
+            disks.usage = usage;
+        }
+        Err(err) => disks.error = Some(err.to_string()),
+    }
+
+    disks
+}
+
+/// A mount as listed by the platform, before any usage is gathered.
+pub(crate) struct MountEntry {
+    pub(crate) source: String,
+    pub(crate) mount_point: String,
+    pub(crate) fstype: String,
+    pub(crate) kind: FilesystemKind,
+    pub(crate) read_only: bool,
+    /// Identify the filesystem (and subvolume) behind the mount, shared by its bind mounts
+    pub(crate) fs_id: String,
+}
+
+/// Builder used to select the partitions to list.
+///
+/// ```ignore
+/// use sys_metrics::disks::*;
+///
+/// let disks = PartitionQuery::new()
+///     .exclude_fstype("squashfs")
+///     .exclude_mount_point("/var/lib/docker/*")
+///     .dedup_bind_mounts()
+///     .run()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PartitionQuery {
+    include_fstypes: Vec<String>,
+    exclude_fstypes: Vec<String>,
+    include_kinds: Vec<FilesystemKind>,
+    include_devices: Vec<String>,
+    exclude_devices: Vec<String>,
+    include_mount_points: Vec<String>,
+    exclude_mount_points: Vec<String>,
+    exclude_read_only: bool,
+    dedup_bind_mounts: bool,
+    skip_usage: bool,
+    timeout: Option<Duration>,
+    kind_overrides: HashMap<String, FilesystemKind>,
+}
+
+/// Compile a list of glob patterns, reporting invalid ones as InvalidInput.
+fn compile_patterns(patterns: &[String]) -> Result<Vec<glob::Pattern>, Error> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern).map_err(|e| Error::new(ErrorKind::InvalidInput, e))
+        })
+        .collect()
+}
+
+/// Check the value against the include (if any) and exclude patterns.
+#[inline]
+fn is_selected(value: &str, include: &[glob::Pattern], exclude: &[glob::Pattern]) -> bool {
+    (include.is_empty() || include.iter().any(|p| p.matches(value)))
+        && !exclude.iter().any(|p| p.matches(value))
+}
+
+impl PartitionQuery {
+    /// Create a query selecting every mount.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only keep the mounts of this fstype (can be called multiple times).
+    pub fn include_fstype<S: Into<String>>(mut self, fstype: S) -> Self {
+        self.include_fstypes.push(fstype.into());
+        self
+    }
+
+    /// Drop the mounts of this fstype.
+    pub fn exclude_fstype<S: Into<String>>(mut self, fstype: S) -> Self {
+        self.exclude_fstypes.push(fstype.into());
+        self
+    }
+
+    /// Only keep the mounts of this [FilesystemKind] (can be called multiple times).
+    ///
+    /// [FilesystemKind]: ../disks/enum.FilesystemKind.html
+    pub fn include_kind(mut self, kind: FilesystemKind) -> Self {
+        self.include_kinds.push(kind);
+        self
+    }
+
+    /// Classify every mount using `fstype` as `kind`, instead of detecting it.
+    ///
+    /// Applied before the selection by [include_kind].
+    ///
+    /// [include_kind]: ../disks/struct.PartitionQuery.html#method.include_kind
+    pub fn filesystem_kind<S: Into<String>>(mut self, fstype: S, kind: FilesystemKind) -> Self {
+        self.kind_overrides.insert(fstype.into(), kind);
+        self
+    }
+
+    /// Only keep the mounts whose device matches the glob (eg: `/dev/nvme*`).
+    pub fn include_device<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.include_devices.push(pattern.into());
+        self
+    }
+
+    /// Drop the mounts whose device matches the glob (eg: `/dev/loop*`).
+    pub fn exclude_device<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.exclude_devices.push(pattern.into());
+        self
+    }
+
+    /// Only keep the mounts whose mount point matches the glob (eg: `/srv/*`).
+    pub fn include_mount_point<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.include_mount_points.push(pattern.into());
+        self
+    }
+
+    /// Drop the mounts whose mount point matches the glob (eg: `/var/lib/docker/*`).
+    pub fn exclude_mount_point<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.exclude_mount_points.push(pattern.into());
+        self
+    }
+
+    /// Drop the read-only mounts.
+    pub fn exclude_read_only(mut self) -> Self {
+        self.exclude_read_only = true;
+        self
+    }
+
+    /// Only keep the first mount of a filesystem (and subvolume), collapsing its bind mounts.
+    pub fn dedup_bind_mounts(mut self) -> Self {
+        self.dedup_bind_mounts = true;
+        self
+    }
+
+    /// Don't call statvfs, the usage of the returned [Disks] is left to 0.
+    ///
+    /// [Disks]: ../disks/struct.Disks.html
+    pub fn skip_usage(mut self) -> Self {
+        self.skip_usage = true;
+        self
+    }
+
+    /// Wait at most `timeout` for the usage of network and FUSE mounts.
+    ///
+    /// See [disk_usage_with_timeout].
+    ///
+    /// [disk_usage_with_timeout]: ../disks/fn.disk_usage_with_timeout.html
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Run the query and return the selected [Disks].
+    ///
+    /// Return an Error of kind InvalidInput if one of the glob patterns is invalid.
+    ///
+    /// [Disks]: ../disks/struct.Disks.html
+    pub fn run(&self) -> Result<Vec<Disks>, Error> {
+        let include_devices = compile_patterns(&self.include_devices)?;
+        let exclude_devices = compile_patterns(&self.exclude_devices)?;
+        let include_mount_points = compile_patterns(&self.include_mount_points)?;
+        let exclude_mount_points = compile_patterns(&self.exclude_mount_points)?;
+
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut vdisks: Vec<Disks> = Vec::new();
+        for entry in get_mount_entries(&self.kind_overrides)? {
+            if (!self.include_fstypes.is_empty() && !self.include_fstypes.contains(&entry.fstype))
+                || self.exclude_fstypes.contains(&entry.fstype)
+                || (!self.include_kinds.is_empty() && !self.include_kinds.contains(&entry.kind))
+                || (self.exclude_read_only && entry.read_only)
+                || !is_selected(&entry.source, &include_devices, &exclude_devices)
+                || !is_selected(
+                    &entry.mount_point,
+                    &include_mount_points,
+                    &exclude_mount_points,
+                )
+            {
+                continue;
+            }
+            if self.dedup_bind_mounts && !seen.insert(entry.fs_id) {
+                continue;
+            }
+
+            if self.skip_usage {
+                vdisks.push(Disks {
+                    name: entry.source,
+                    mount_point: entry.mount_point,
+                    fstype: entry.fstype,
+                    kind: entry.kind,
+                    ..Default::default()
+                });
+            } else {
+                vdisks.push(build_disks(
+                    entry.source,
+                    entry.mount_point,
+                    entry.fstype,
+                    entry.kind,
+                    self.timeout,
+                ));
+            }
+        }
+
+        Ok(vdisks)
+    }
 }
 
 /// Detect if a filesystem is for a physical drive or not.
 /// This is not 100% true, but it's true enough for me.
-/// The better approach would be to read the filesystem from /proc/filesystems
-/// maybe construct a lazy_static array of filesystem.
+/// Linux relies on /proc/filesystems instead, see sys/linux/filesystems.rs.
+#[cfg(target_os = "macos")]
 pub(crate) fn is_physical_filesys(filesystem: &str) -> bool {
     matches!(
         filesystem,
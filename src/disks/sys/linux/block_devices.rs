@@ -0,0 +1,115 @@
+use crate::disks::{BlockDevice, BlockPartition};
+use crate::{read_attr, read_attr_bool, read_parsed, SYSFS_SECTOR_SIZE};
+
+use std::io::Error;
+use std::{fs, path::Path};
+
+/// Parse the `dev` attribute (`major:minor`).
+#[inline]
+pub(crate) fn read_dev_numbers<P: AsRef<Path>>(path: P) -> Option<(u32, u32)> {
+    let dev = read_attr(path)?;
+    let (major, minor) = dev.split_once(':')?;
+
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// List the entries of a directory (like `holders/` or `slaves/`) by name.
+pub(crate) fn list_dir_names<P: AsRef<Path>>(path: P) -> Vec<String> {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Get the active scheduler out of `none [mq-deadline] kyber bfq`.
+#[inline]
+fn active_scheduler(content: &str) -> Option<String> {
+    let start = content.find('[')?;
+    let end = content[start..].find(']')?;
+
+    Some(content[start + 1..start + end].to_owned())
+}
+
+fn get_partitions_of(device: &Path, name: &str) -> Vec<BlockPartition> {
+    let mut partitions: Vec<BlockPartition> = list_dir_names(device)
+        .into_iter()
+        // Partitions are the subdirectories prefixed by the device's name
+        // which contains a `partition` attribute.
+        .filter(|entry| entry.starts_with(name))
+        .filter_map(|entry| {
+            let path = device.join(&entry);
+            let number = read_parsed(path.join("partition"))?;
+            let (major, minor) = read_dev_numbers(path.join("dev")).unwrap_or_default();
+
+            Some(BlockPartition {
+                major,
+                minor,
+                number,
+                start: read_parsed(path.join("start")).unwrap_or(0u64) * SYSFS_SECTOR_SIZE,
+                size: read_parsed(path.join("size")).unwrap_or(0u64) * SYSFS_SECTOR_SIZE,
+                read_only: read_attr_bool(path.join("ro")),
+                holders: list_dir_names(path.join("holders")),
+                name: entry,
+            })
+        })
+        .collect();
+    partitions.sort_by_key(|part| part.number);
+    partitions
+}
+
+fn get_block_device(name: String) -> BlockDevice {
+    let path = Path::new("/sys/block").join(&name);
+    let device = path.join("device");
+    let queue = path.join("queue");
+    let (major, minor) = read_dev_numbers(path.join("dev")).unwrap_or_default();
+
+    BlockDevice {
+        major,
+        minor,
+        size: read_parsed(path.join("size")).unwrap_or(0u64) * SYSFS_SECTOR_SIZE,
+        logical_sector_size: read_parsed(queue.join("logical_block_size"))
+            .unwrap_or(SYSFS_SECTOR_SIZE),
+        physical_sector_size: read_parsed(queue.join("physical_block_size"))
+            .unwrap_or(SYSFS_SECTOR_SIZE),
+        rotational: read_attr_bool(queue.join("rotational")),
+        removable: read_attr_bool(path.join("removable")),
+        read_only: read_attr_bool(path.join("ro")),
+        model: read_attr(device.join("model")),
+        vendor: read_attr(device.join("vendor")),
+        // SCSI/ATA expose it under device/, NVMe and virtio directly on the block device.
+        serial: read_attr(device.join("serial")).or_else(|| read_attr(path.join("serial"))),
+        wwn: read_attr(device.join("wwid"))
+            .or_else(|| read_attr(path.join("wwid")))
+            .or_else(|| read_attr(device.join("wwn"))),
+        scheduler: read_attr(queue.join("scheduler")).and_then(|val| active_scheduler(&val)),
+        // Only SCSI devices have a device/queue_depth, fallback to the block layer's one.
+        queue_depth: read_parsed(device.join("queue_depth"))
+            .or_else(|| read_parsed(queue.join("nr_requests"))),
+        partitions: get_partitions_of(&path, &name),
+        holders: list_dir_names(path.join("holders")),
+        slaves: list_dir_names(path.join("slaves")),
+        name,
+    }
+}
+
+/// Get the list of [BlockDevice] present in `/sys/block` along with their partitions.
+///
+/// Attributes which are not exposed by the device's driver are left to None.
+///
+/// [BlockDevice]: ../disks/struct.BlockDevice.html
+pub fn get_block_devices() -> Result<Vec<BlockDevice>, Error> {
+    let mut names: Vec<String> = fs::read_dir("/sys/block")?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+
+    Ok(names.into_iter().map(get_block_device).collect())
+}
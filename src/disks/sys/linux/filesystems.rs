@@ -0,0 +1,84 @@
+use crate::disks::{filesystem_kind_from_name, FilesystemKind, Mount};
+
+use std::collections::HashMap;
+use std::io::Error;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Read `/proc/filesystems` into a map of fstype -> nodev.
+pub(crate) fn get_filesystems() -> Result<HashMap<String, bool>, Error> {
+    let file = File::open("/proc/filesystems")?;
+    let mut filesystems = HashMap::new();
+    let mut file = BufReader::with_capacity(1024, file);
+
+    let mut line = String::with_capacity(32);
+    while file.read_line(&mut line)? != 0 {
+        // Lines are either `nodev\tproc` or `\text4`
+        let mut fields = line.split_whitespace();
+        match (fields.next(), fields.next()) {
+            (Some("nodev"), Some(fstype)) => {
+                filesystems.insert(fstype.to_owned(), true);
+            }
+            (Some(fstype), None) => {
+                filesystems.insert(fstype.to_owned(), false);
+            }
+            _ => {}
+        }
+        line.clear();
+    }
+
+    Ok(filesystems)
+}
+
+/// Tell if the mount is stored on a virtual block device (loop, ram, zram).
+fn is_virtual_device(mount: &Mount) -> bool {
+    let dev = Path::new(&format!("/sys/dev/block/{}:{}", mount.major, mount.minor))
+        .canonicalize()
+        .ok();
+
+    match dev.as_ref().and_then(|dev| dev.file_name()?.to_str()) {
+        Some(name) => {
+            name.starts_with("loop") || name.starts_with("ram") || name.starts_with("zram")
+        }
+        None => false,
+    }
+}
+
+/// Classify a mount using the overrides, its name, `/proc/filesystems` and its backing device.
+pub(crate) fn classify_mount(
+    mount: &Mount,
+    filesystems: &HashMap<String, bool>,
+    overrides: &HashMap<String, FilesystemKind>,
+) -> FilesystemKind {
+    if let Some(kind) = overrides.get(&mount.fstype) {
+        return *kind;
+    }
+    if let Some(kind) = filesystem_kind_from_name(&mount.fstype) {
+        return kind;
+    }
+
+    let requires_dev = match filesystems.get(&mount.fstype) {
+        Some(nodev) => !nodev,
+        // Unknown to the kernel's list (eg: subtypes), rely on the device number,
+        // an anonymous device has the major 0.
+        None => mount.major != 0,
+    };
+    if !requires_dev {
+        FilesystemKind::Pseudo
+    } else if is_virtual_device(mount) {
+        FilesystemKind::Virtual
+    } else {
+        FilesystemKind::Physical
+    }
+}
+
+/// Return the [FilesystemKind] of a [Mount].
+///
+/// [FilesystemKind]: ../disks/enum.FilesystemKind.html
+/// [Mount]: ../disks/struct.Mount.html
+pub fn get_filesystem_kind(mount: &Mount) -> Result<FilesystemKind, Error> {
+    Ok(classify_mount(mount, &get_filesystems()?, &HashMap::new()))
+}
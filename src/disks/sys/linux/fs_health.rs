@@ -0,0 +1,230 @@
+use crate::disks::{
+    find_mount, get_mounts, list_dir_names, BtrfsAllocation, BtrfsDeviceErrors, BtrfsHealth,
+    Ext4Health, FilesystemHealth, Mount, XfsErrorConfig, XfsHealth,
+};
+use crate::{read_attr, read_attr_bool, read_parsed};
+
+use std::ffi::CString;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+fn get_ext4_health(device: &str) -> Option<Ext4Health> {
+    let path = Path::new("/sys/fs/ext4").join(device);
+    if !path.exists() {
+        return None;
+    }
+    // Time attributes are 0 and functions empty when no error happened
+    let time = |name: &str| read_parsed(path.join(name)).filter(|time| *time != 0);
+
+    Some(Ext4Health {
+        errors_count: read_parsed(path.join("errors_count")).unwrap_or(0),
+        warning_count: read_parsed(path.join("warning_count")).unwrap_or(0),
+        msg_count: read_parsed(path.join("msg_count")).unwrap_or(0),
+        first_error_time: time("first_error_time"),
+        first_error_func: read_attr(path.join("first_error_func")),
+        last_error_time: time("last_error_time"),
+        last_error_func: read_attr(path.join("last_error_func")),
+        lifetime_write_kbytes: read_parsed(path.join("lifetime_write_kbytes")).unwrap_or(0),
+        delayed_allocation_blocks: read_parsed(path.join("delayed_allocation_blocks")).unwrap_or(0),
+    })
+}
+
+/// Tell if the filesystem at `mount_point` was shut down (statvfs fails with EIO).
+fn is_shutdown(mount_point: &str) -> bool {
+    let path = match CString::new(mount_point) {
+        Ok(path) => path,
+        Err(_) => return false,
+    };
+    let mut statvfs = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    if unsafe { libc::statvfs(path.as_ptr(), statvfs.as_mut_ptr()) } == -1 {
+        return Error::last_os_error().raw_os_error() == Some(libc::EIO);
+    }
+    false
+}
+
+fn get_xfs_health(device: &str, mount_point: &str) -> Option<XfsHealth> {
+    let path = Path::new("/sys/fs/xfs").join(device);
+    if !path.exists() {
+        return None;
+    }
+
+    let stats = read_attr(path.join("stats/stats"))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| {
+                    let mut fields = line.split_whitespace();
+                    let name = fields.next()?.to_owned();
+                    Some((name, fields.filter_map(|val| val.parse().ok()).collect()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let error = path.join("error");
+    let mut error_config = Vec::new();
+    for class in list_dir_names(&error) {
+        let class_path = error.join(&class);
+        if !class_path.is_dir() {
+            continue;
+        }
+        for err in list_dir_names(&class_path) {
+            let err_path = class_path.join(&err);
+            error_config.push(XfsErrorConfig {
+                max_retries: read_parsed(err_path.join("max_retries")).unwrap_or(0),
+                retry_timeout_seconds: read_parsed(err_path.join("retry_timeout_seconds"))
+                    .unwrap_or(0),
+                class: class.clone(),
+                error: err,
+            });
+        }
+    }
+
+    Some(XfsHealth {
+        stats,
+        shutdown: is_shutdown(mount_point),
+        fail_at_unmount: read_attr_bool(error.join("fail_at_unmount")),
+        error_config,
+    })
+}
+
+/// Parse the `error_stats` file (`write_errs 0\nread_errs 0...`).
+fn get_btrfs_device_errors(devid: u64, path: &Path) -> BtrfsDeviceErrors {
+    let mut errors = BtrfsDeviceErrors {
+        devid,
+        ..Default::default()
+    };
+
+    if let Some(content) = read_attr(path.join("error_stats")) {
+        for line in content.lines() {
+            let (name, value) = match line.split_once(' ') {
+                Some((name, value)) => (name, value.trim().parse().unwrap_or(0)),
+                None => continue,
+            };
+            match name {
+                "write_errs" => errors.write_errs = value,
+                "read_errs" => errors.read_errs = value,
+                "flush_errs" => errors.flush_errs = value,
+                "corruption_errs" => errors.corruption_errs = value,
+                "generation_errs" => errors.generation_errs = value,
+                _ => {}
+            }
+        }
+    }
+
+    errors
+}
+
+fn get_btrfs_health(device: Option<&str>) -> Option<BtrfsHealth> {
+    let device = device?;
+    // The filesystem is identified by its uuid, find the one holding our device
+    let uuid = list_dir_names("/sys/fs/btrfs").into_iter().find(|uuid| {
+        Path::new("/sys/fs/btrfs")
+            .join(uuid)
+            .join("devices")
+            .join(device)
+            .exists()
+    })?;
+    let path = Path::new("/sys/fs/btrfs").join(&uuid);
+
+    let devinfo = path.join("devinfo");
+    let devices = list_dir_names(&devinfo)
+        .into_iter()
+        .filter_map(|devid| {
+            let id = devid.parse().ok()?;
+            Some(get_btrfs_device_errors(id, &devinfo.join(devid)))
+        })
+        .collect();
+
+    let mut allocation = Vec::new();
+    for block_group in ["data", "metadata", "system"] {
+        let group_path = path.join("allocation").join(block_group);
+        for profile in list_dir_names(&group_path) {
+            let profile_path = group_path.join(&profile);
+            // Profiles are the only subdirectories
+            if !profile_path.is_dir() {
+                continue;
+            }
+            allocation.push(BtrfsAllocation {
+                block_group: block_group.to_owned(),
+                total_bytes: read_parsed(profile_path.join("total_bytes")).unwrap_or(0),
+                used_bytes: read_parsed(profile_path.join("used_bytes")).unwrap_or(0),
+                profile,
+            });
+        }
+    }
+
+    Some(BtrfsHealth {
+        label: read_attr(path.join("label")),
+        exclusive_operation: read_attr(path.join("exclusive_operation")),
+        devices,
+        allocation,
+        uuid,
+    })
+}
+
+/// Return the [FilesystemHealth] of a [Mount].
+///
+/// The error counters are read from `/sys/fs/ext4/<dev>/`, `/sys/fs/xfs/<dev>/`
+/// and `/sys/fs/btrfs/<uuid>/`. Other filesystems only get the generic information.
+///
+/// [FilesystemHealth]: ../disks/struct.FilesystemHealth.html
+/// [Mount]: ../disks/struct.Mount.html
+pub fn get_filesystem_health(mount: &Mount) -> FilesystemHealth {
+    // btrfs uses an anonymous device number, so resolve its source instead
+    let device = Path::new(&format!("/sys/dev/block/{}:{}", mount.major, mount.minor))
+        .canonicalize()
+        .or_else(|_| Path::new(&mount.source).canonicalize())
+        .ok()
+        .and_then(|path| Some(path.file_name()?.to_str()?.to_owned()));
+
+    let mut health = FilesystemHealth {
+        mount_point: mount.mount_point.clone(),
+        fstype: mount.fstype.clone(),
+        read_only: mount.is_read_only(),
+        remounted_read_only: mount.super_options.iter().any(|opt| opt == "ro")
+            && !mount.mount_options.iter().any(|opt| opt == "ro"),
+        errors_behavior: mount
+            .super_options
+            .iter()
+            .find_map(|opt| opt.strip_prefix("errors="))
+            .map(|opt| opt.to_owned()),
+        ..Default::default()
+    };
+    match mount.fstype.as_str() {
+        "ext2" | "ext3" | "ext4" => health.ext4 = device.as_deref().and_then(get_ext4_health),
+        "xfs" => {
+            health.xfs = device
+                .as_deref()
+                .and_then(|device| get_xfs_health(device, &mount.mount_point))
+        }
+        "btrfs" => health.btrfs = get_btrfs_health(device.as_deref()),
+        _ => {}
+    }
+    health.device = device;
+
+    health
+}
+
+/// Return the [FilesystemHealth] of the filesystem mounted at `mount_point`.
+///
+/// See [get_filesystem_health].
+///
+/// [FilesystemHealth]: ../disks/struct.FilesystemHealth.html
+/// [get_filesystem_health]: ../disks/fn.get_filesystem_health.html
+pub fn filesystem_health<P>(mount_point: P) -> Result<FilesystemHealth, Error>
+where
+    P: AsRef<Path>,
+{
+    let mount_point = mount_point.as_ref();
+    let mounts = get_mounts()?;
+    match find_mount(&mounts, mount_point)
+        .filter(|mount| Path::new(&mount.mount_point) == mount_point)
+    {
+        Some(mount) => Ok(get_filesystem_health(mount)),
+        None => Err(Error::new(
+            ErrorKind::NotFound,
+            "The given path is not a mount point",
+        )),
+    }
+}
@@ -0,0 +1,209 @@
+use crate::disks::{
+    classify_mount, find_mount, get_filesystems, get_mounts, unescape_octal, FilesystemKind,
+    FstabEntry, FstabMismatch, FstabReport, Mount,
+};
+
+use std::collections::HashMap;
+use std::io::Error;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Resolve the spec of an entry to the device path, following `/dev/disk/by-*` links.
+fn resolve_spec(spec: &str) -> Option<String> {
+    let path = match spec.split_once('=') {
+        Some(("UUID", uuid)) => format!("/dev/disk/by-uuid/{}", uuid),
+        Some(("LABEL", label)) => format!("/dev/disk/by-label/{}", label),
+        Some(("PARTUUID", uuid)) => format!("/dev/disk/by-partuuid/{}", uuid),
+        Some(("PARTLABEL", label)) => format!("/dev/disk/by-partlabel/{}", label),
+        _ if spec.starts_with("/dev/") => spec.to_owned(),
+        // Network shares, pseudo filesystems, ...
+        _ => return None,
+    };
+
+    Path::new(&path)
+        .canonicalize()
+        .ok()
+        .and_then(|path| path.to_str().map(|path| path.to_owned()))
+}
+
+/// Options which only matter to mount(8)/systemd and are never shown by the kernel.
+#[inline]
+fn is_userspace_option(option: &str) -> bool {
+    matches!(
+        option,
+        "defaults"
+            | "auto"
+            | "noauto"
+            | "nofail"
+            | "user"
+            | "users"
+            | "nouser"
+            | "owner"
+            | "group"
+            | "_netdev"
+            | "bg"
+            | "fg"
+            | "rw"
+    ) || option.starts_with("x-")
+        || option.starts_with("comment=")
+}
+
+/// Default flags the kernel never shows (their negation is shown instead).
+#[inline]
+fn is_default_option(option: &str) -> bool {
+    matches!(
+        option,
+        "exec" | "suid" | "dev" | "async" | "atime" | "diratime"
+    )
+}
+
+/// Parse an octal mask (`022`, `0022`).
+#[inline]
+fn parse_mask(value: &str) -> Option<u32> {
+    u32::from_str_radix(value, 8).ok()
+}
+
+/// Tell if the mount has the option, taking into account how the kernel rewrites some of them.
+fn has_option(mount: &Mount, option: &str) -> bool {
+    let options = || mount.mount_options.iter().chain(mount.super_options.iter());
+    let value_of = |name: &str| options().find_map(|opt| opt.strip_prefix(name)?.strip_prefix('='));
+
+    match option.split_once('=') {
+        // nfsvers= is shown as vers=, and the minor version negotiated (vers=4 -> vers=4.2)
+        Some(("nfsvers", version)) | Some(("vers", version)) if mount.fstype.starts_with("nfs") => {
+            value_of("vers").is_some_and(|actual| {
+                actual == version || actual.starts_with(&format!("{}.", version))
+            })
+        }
+        // umask= is split into fmask= and dmask=, masks are shown zero-padded
+        Some(("umask", mask)) => {
+            let mask = parse_mask(mask);
+            mask.is_some()
+                && value_of("fmask").and_then(parse_mask) == mask
+                && value_of("dmask").and_then(parse_mask) == mask
+        }
+        Some((name @ ("fmask" | "dmask"), mask)) => {
+            parse_mask(mask).is_some() && value_of(name).and_then(parse_mask) == parse_mask(mask)
+        }
+        _ => options().any(|opt| opt == option),
+    }
+}
+
+/// Return a Vec of [FstabEntry] parsed from `/etc/fstab`.
+///
+/// `UUID=`, `LABEL=`, `PARTUUID=` and `PARTLABEL=` are resolved through `/dev/disk/by-*`.
+///
+/// [FstabEntry]: ../disks/struct.FstabEntry.html
+pub fn get_fstab() -> Result<Vec<FstabEntry>, Error> {
+    let file = File::open("/etc/fstab")?;
+    let mut entries: Vec<FstabEntry> = Vec::new();
+    let mut file = BufReader::with_capacity(2048, file);
+
+    let mut line = String::with_capacity(256);
+    while file.read_line(&mut line)? != 0 {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            line.clear();
+            continue;
+        }
+
+        let mut fields = trimmed.split_whitespace();
+        if let (Some(spec), Some(mount_point), Some(fstype)) =
+            (fields.next(), fields.next(), fields.next())
+        {
+            let spec = unescape_octal(spec);
+            entries.push(FstabEntry {
+                device: resolve_spec(&spec),
+                spec,
+                mount_point: unescape_octal(mount_point),
+                fstype: fstype.to_owned(),
+                options: fields
+                    .next()
+                    .unwrap_or("defaults")
+                    .split(',')
+                    .map(unescape_octal)
+                    .collect(),
+                dump: fields.next().and_then(|v| v.parse().ok()).unwrap_or(0),
+                pass: fields.next().and_then(|v| v.parse().ok()).unwrap_or(0),
+            });
+        }
+        line.clear();
+    }
+
+    Ok(entries)
+}
+
+/// Compare an [FstabEntry] with the [Mount] at its mount point.
+///
+/// Return None if the mount matches the entry.
+///
+/// [FstabEntry]: ../disks/struct.FstabEntry.html
+/// [Mount]: ../disks/struct.Mount.html
+pub fn compare_fstab_entry(entry: &FstabEntry, mount: &Mount) -> Option<FstabMismatch> {
+    let missing_options: Vec<String> = entry
+        .options
+        .iter()
+        // ro/rw are checked on their own with read_only_mismatch
+        .filter(|opt| {
+            !is_userspace_option(opt) && !is_default_option(opt) && *opt != "ro" && *opt != "rw"
+        })
+        .filter(|opt| !has_option(mount, opt))
+        .cloned()
+        .collect();
+    let read_only_mismatch = entry.options.iter().any(|opt| opt == "ro") != mount.is_read_only();
+    let device_mismatch = match (&entry.device, resolve_spec(&mount.source)) {
+        (Some(expected), Some(actual)) => *expected != actual,
+        _ => false,
+    };
+
+    if missing_options.is_empty() && !read_only_mismatch && !device_mismatch {
+        return None;
+    }
+
+    Some(FstabMismatch {
+        entry: entry.clone(),
+        mount: mount.clone(),
+        missing_options,
+        read_only_mismatch,
+        device_mismatch,
+    })
+}
+
+/// Compare `/etc/fstab` with the live mounts and return a [FstabReport].
+///
+/// Entries with `noauto` and swaps are not expected to be mounted. Only the physical
+/// and network mounts are checked for a missing entry, as the pseudo filesystems
+/// (proc, cgroup, tmpfs, ...) are mounted by the init system.
+///
+/// [FstabReport]: ../disks/struct.FstabReport.html
+pub fn reconcile_fstab() -> Result<FstabReport, Error> {
+    let entries = get_fstab()?;
+    let mounts = get_mounts()?;
+    let filesystems = get_filesystems()?;
+    let mut report = FstabReport::default();
+
+    for entry in entries.iter().filter(|entry| entry.is_automount()) {
+        match find_mount(&mounts, Path::new(&entry.mount_point))
+            .filter(|mount| mount.mount_point == entry.mount_point)
+        {
+            Some(mount) => report.mismatches.extend(compare_fstab_entry(entry, mount)),
+            None => report.not_mounted.push(entry.clone()),
+        }
+    }
+
+    report.not_in_fstab = mounts
+        .into_iter()
+        .filter(|mount| {
+            matches!(
+                classify_mount(mount, &filesystems, &HashMap::new()),
+                FilesystemKind::Physical | FilesystemKind::Network
+            )
+        })
+        .filter(|mount| !entries.iter().any(|e| e.mount_point == mount.mount_point))
+        .collect();
+
+    Ok(report)
+}
@@ -1,4 +1,4 @@
-use crate::disks::IoBlock;
+use crate::disks::{is_loop_device, IoBlock};
 
 use std::io::{Error, ErrorKind};
 use std::{
@@ -50,6 +50,7 @@ fn _get_ioblocks(physical: bool) -> Result<Vec<IoBlock>, Error> {
             write_count: write_count.parse().unwrap(),
             write_bytes: write_bytes.parse::<u64>().unwrap() * DISK_SECTOR_SIZE,
             busy_time: busy_time.parse().unwrap(),
+            is_loop: is_loop_device(name),
         });
         line.clear();
     }
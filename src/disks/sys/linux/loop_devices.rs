@@ -0,0 +1,58 @@
+use crate::disks::LoopDevice;
+use crate::{read_attr, read_attr_bool, read_parsed, SYSFS_SECTOR_SIZE};
+
+use std::io::Error;
+use std::{fs, path::Path};
+
+/// Whether the block device `name` is an attached loop device or one of its partitions.
+///
+/// Partitions of a loop device don't necessarily use the loop major (they get the
+/// blkext one when `max_part` is 0), so rely on the `loop/` directory of the disk.
+pub(crate) fn is_loop_device(name: &str) -> bool {
+    let path = Path::new("/sys/class/block").join(name.replace('/', "!"));
+    if path.join("loop").exists() {
+        return true;
+    }
+    // The sysfs directory of a partition is inside the one of its disk
+    path.join("partition").exists()
+        && fs::canonicalize(&path)
+            .ok()
+            .and_then(|path| Some(path.parent()?.join("loop").exists()))
+            .unwrap_or(false)
+}
+
+/// Return a Vec of [LoopDevice] for every attached loop device.
+///
+/// Detached loop devices (without a backing file) are not listed.
+///
+/// [LoopDevice]: ../disks/struct.LoopDevice.html
+pub fn get_loop_devices() -> Result<Vec<LoopDevice>, Error> {
+    let mut names: Vec<String> = fs::read_dir("/sys/block")?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with("loop"))
+        .collect();
+    // Sort them numerically (loop2 before loop10)
+    names.sort_by_key(|name| name[4..].parse::<u32>().unwrap_or(u32::MAX));
+
+    Ok(names
+        .into_iter()
+        .filter_map(|name| {
+            let path = Path::new("/sys/block").join(&name);
+            // The loop/ directory only exists while the device is attached
+            let backing_file = read_attr(path.join("loop/backing_file"))?;
+
+            Some(LoopDevice {
+                backing_file,
+                offset: read_parsed(path.join("loop/offset")).unwrap_or(0),
+                size_limit: read_parsed(path.join("loop/sizelimit")).unwrap_or(0),
+                size: read_parsed(path.join("size")).unwrap_or(0) * SYSFS_SECTOR_SIZE,
+                autoclear: read_attr_bool(path.join("loop/autoclear")),
+                partscan: read_attr_bool(path.join("loop/partscan")),
+                read_only: read_attr_bool(path.join("ro")),
+                direct_io: read_attr_bool(path.join("loop/dio")),
+                name,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,197 @@
+use crate::disks::{MdArray, MdMember, MdSync};
+use crate::read_attr;
+
+use std::io::{Error, ErrorKind};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Parse a member of the mdstat header line, like `sdb1[1](F)`.
+fn parse_member(token: &str) -> Option<MdMember> {
+    let (name, rest) = token.split_once('[')?;
+    let (descriptor, flags) = rest.split_once(']')?;
+
+    Some(MdMember {
+        name: name.to_owned(),
+        descriptor: descriptor.parse().ok()?,
+        faulty: flags.contains("(F)"),
+        spare: flags.contains("(S)"),
+        write_mostly: flags.contains("(W)"),
+        replacement: flags.contains("(R)"),
+        ..Default::default()
+    })
+}
+
+/// Parse the header line: `md0 : active raid1 sdb1[1] sda1[0]`.
+fn parse_header(name: &str, rest: &str) -> MdArray {
+    let mut array = MdArray {
+        name: name.to_owned(),
+        ..Default::default()
+    };
+
+    let mut tokens = rest.split_whitespace().peekable();
+    array.state = tokens.next().unwrap_or_default().to_owned();
+    while let Some(token) = tokens.peek() {
+        if token.contains('[') {
+            break;
+        }
+        match *token {
+            "(read-only)" | "(auto-read-only)" => array.read_only = true,
+            level => array.level = level.to_owned(),
+        }
+        tokens.next();
+    }
+    array.members = tokens.filter_map(parse_member).collect();
+
+    array
+}
+
+/// Parse the status line: `1048512 blocks super 1.2 [2/2] [UU]`.
+fn parse_status(array: &mut MdArray, line: &str) {
+    let mut tokens = line.split_whitespace();
+    if let (Some(blocks), Some("blocks")) = (tokens.next(), tokens.next()) {
+        array.size = blocks.parse::<u64>().unwrap_or(0) * 1024;
+    }
+    if let Some((raid, active)) = tokens
+        .filter_map(|token| token.strip_prefix('[')?.strip_suffix(']')?.split_once('/'))
+        .next()
+    {
+        array.raid_disks = raid.parse().unwrap_or(0);
+        array.active_disks = active.parse().unwrap_or(0);
+    }
+}
+
+/// Parse the progress line:
+/// `[==>....]  recovery = 12.6% (37043392/293039104) finish=127.5min speed=33440K/sec`
+/// or the pending one: `resync=DELAYED`.
+fn parse_sync(line: &str) -> Option<MdSync> {
+    let line = match line.find(']') {
+        Some(pos) if line.starts_with('[') => &line[pos + 1..],
+        _ => line,
+    };
+    let (action, rest) = line.split_once('=')?;
+    let action = action.trim();
+    if !matches!(
+        action,
+        "resync" | "recovery" | "check" | "repair" | "reshape"
+    ) {
+        return None;
+    }
+
+    let mut sync = MdSync {
+        action: action.to_owned(),
+        ..Default::default()
+    };
+    for token in rest.split_whitespace() {
+        if let Some(progress) = token.strip_suffix('%') {
+            sync.progress = progress.parse().unwrap_or(0.0);
+        } else if let Some((done, total)) = token
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))
+            .and_then(|t| t.split_once('/'))
+        {
+            sync.done = done.parse().unwrap_or(0);
+            sync.total = total.parse().unwrap_or(0);
+        } else if let Some(finish) = token.strip_prefix("finish=") {
+            sync.finish = finish
+                .strip_suffix("min")
+                .and_then(|min| min.parse::<f64>().ok())
+                .map(|min| min * 60.0);
+        } else if let Some(speed) = token.strip_prefix("speed=") {
+            sync.speed = speed
+                .strip_suffix("K/sec")
+                .and_then(|kb| kb.parse().ok())
+                .unwrap_or(0);
+        } else if token == "DELAYED" || token == "PENDING" {
+            sync.pending = true;
+        }
+    }
+
+    Some(sync)
+}
+
+/// Complete the array with the more accurate information from `/sys/block/mdX/md/`.
+fn enrich_from_sysfs(array: &mut MdArray) {
+    let md = Path::new("/sys/block").join(&array.name).join("md");
+    if !md.exists() {
+        return;
+    }
+
+    if let Some(state) = read_attr(md.join("array_state")) {
+        array.read_only |= state == "readonly" || state == "read-auto";
+        array.state = state;
+    }
+    if let Some(degraded) = read_attr(md.join("degraded")).and_then(|d| d.parse().ok()) {
+        array.degraded = degraded;
+    }
+
+    for member in array.members.iter_mut() {
+        let dev = md.join(format!("dev-{}", member.name));
+        member.slot = read_attr(dev.join("slot")).and_then(|slot| slot.parse().ok());
+        if let Some(state) = read_attr(dev.join("state")) {
+            for flag in state.split(',') {
+                match flag {
+                    "faulty" => member.faulty = true,
+                    "spare" => member.spare = true,
+                    "write_mostly" => member.write_mostly = true,
+                    "replacement" => member.replacement = true,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Parse the content of `/proc/mdstat`, without the sysfs information.
+pub fn parse_mdstat<R: BufRead>(mut reader: R) -> Result<Vec<MdArray>, Error> {
+    let mut arrays: Vec<MdArray> = Vec::new();
+
+    let mut line = String::with_capacity(256);
+    while reader.read_line(&mut line)? != 0 {
+        let trimmed = line.trim();
+        // Lines of an array are indented, its header is not
+        if !line.starts_with(char::is_whitespace) {
+            if let Some((name, rest)) = trimmed.split_once(" : ") {
+                if name.starts_with("md") {
+                    arrays.push(parse_header(name, rest));
+                }
+            }
+        } else if let Some(array) = arrays.last_mut() {
+            if array.raid_disks == 0 && trimmed.contains(" blocks") {
+                parse_status(array, trimmed);
+            } else if array.sync.is_none() {
+                array.sync = parse_sync(trimmed);
+            }
+        }
+        line.clear();
+    }
+
+    for array in arrays.iter_mut() {
+        array.degraded = array.raid_disks.saturating_sub(array.active_disks);
+    }
+
+    Ok(arrays)
+}
+
+/// Return a Vec of [MdArray] for every software RAID array of the host.
+///
+/// Arrays are read from `/proc/mdstat` and completed with `/sys/block/md*/md/`.
+/// If the md driver is not loaded (no `/proc/mdstat`), an empty Vec is returned.
+///
+/// [MdArray]: ../disks/struct.MdArray.html
+pub fn get_md_arrays() -> Result<Vec<MdArray>, Error> {
+    let file = match File::open("/proc/mdstat") {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut arrays = parse_mdstat(BufReader::with_capacity(2048, file))?;
+
+    for array in arrays.iter_mut() {
+        enrich_from_sysfs(array);
+    }
+
+    Ok(arrays)
+}
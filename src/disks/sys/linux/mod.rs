@@ -1,5 +1,23 @@
+mod block_devices;
+mod filesystems;
+mod fs_health;
+mod fstab;
 mod ioblocks;
+mod loop_devices;
+mod md_arrays;
+mod mount_device;
+mod mounts;
+mod nfs_mountstats;
 mod partitions;
 
+pub use block_devices::*;
+pub use filesystems::*;
+pub use fs_health::*;
+pub use fstab::*;
 pub use ioblocks::*;
+pub use loop_devices::*;
+pub use md_arrays::*;
+pub use mount_device::*;
+pub use mounts::*;
+pub use nfs_mountstats::*;
 pub use partitions::*;
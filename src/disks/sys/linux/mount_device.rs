@@ -0,0 +1,193 @@
+use crate::disks::{
+    get_mounts, list_dir_names, read_dev_numbers, DeviceMapper, DeviceMapperKind, Mount,
+    MountDevice,
+};
+use crate::read_attr;
+
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// Find the mount holding `path` (the one with the longest matching mount point).
+pub(crate) fn find_mount<'a>(mounts: &'a [Mount], path: &Path) -> Option<&'a Mount> {
+    mounts
+        .iter()
+        .filter(|mount| path.starts_with(&mount.mount_point))
+        // In case of over-mounts, the last one listed is the visible one
+        .fold(None, |best: Option<&Mount>, mount| match best {
+            Some(b) if b.mount_point.len() > mount.mount_point.len() => best,
+            _ => Some(mount),
+        })
+}
+
+/// Resolve the sysfs directory of the device `major:minor`.
+#[inline]
+fn sysfs_dev_path(major: u32, minor: u32) -> Option<PathBuf> {
+    Path::new(&format!("/sys/dev/block/{}:{}", major, minor))
+        .canonicalize()
+        .ok()
+}
+
+/// Filesystems like btrfs report an anonymous device number, in
+/// that case fallback to the device the mount's source points to.
+fn resolve_from_source(source: &str) -> Option<(u32, u32, PathBuf)> {
+    if !source.starts_with("/dev/") {
+        return None;
+    }
+    // Follow symlinks such as /dev/mapper/xxx -> /dev/dm-0
+    let dev = Path::new(source).canonicalize().ok()?;
+    let name = dev.file_name()?.to_str()?;
+    let (major, minor) = read_dev_numbers(format!("/sys/class/block/{}/dev", name))?;
+
+    Some((major, minor, sysfs_dev_path(major, minor)?))
+}
+
+/// Split a LVM dm name (`vg-lv`, with `-` in names doubled) into (vg, lv).
+fn split_lvm_name(name: &str) -> Option<(String, String)> {
+    let bytes = name.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'-' {
+            if bytes.get(i + 1) == Some(&b'-') {
+                i += 2;
+                continue;
+            }
+            let (vg, lv) = (&name[..i], &name[i + 1..]);
+            return Some((vg.replace("--", "-"), lv.replace("--", "-")));
+        }
+        i += 1;
+    }
+
+    None
+}
+
+fn get_device_mapper(device_name: &str) -> Option<DeviceMapper> {
+    let dm = Path::new("/sys/block").join(device_name).join("dm");
+    let name = read_attr(dm.join("name"))?;
+    let uuid = read_attr(dm.join("uuid")).unwrap_or_default();
+
+    let kind = if uuid.starts_with("LVM-") {
+        DeviceMapperKind::Lvm
+    } else if uuid.starts_with("CRYPT-") {
+        DeviceMapperKind::Crypt
+    } else if uuid.starts_with("mpath-") {
+        DeviceMapperKind::Multipath
+    } else {
+        DeviceMapperKind::Other
+    };
+    let (vg_name, lv_name) = match kind {
+        DeviceMapperKind::Lvm => split_lvm_name(&name).unzip(),
+        _ => (None, None),
+    };
+
+    Some(DeviceMapper {
+        device_name: device_name.to_owned(),
+        name,
+        uuid,
+        kind,
+        vg_name,
+        lv_name,
+    })
+}
+
+/// Walk the slaves of `device_name` down to the whole disks backing it.
+fn walk_slaves(device_name: &str, resolved: &mut MountDevice, depth: u8) {
+    // Protect against a (bogus) cycle in sysfs
+    if depth > 16 {
+        return;
+    }
+
+    let class = Path::new("/sys/class/block").join(device_name);
+    // A partition: its parent directory is the disk
+    if class.join("partition").exists() {
+        if let Some(disk) = class
+            .canonicalize()
+            .ok()
+            .and_then(|path| Some(path.parent()?.file_name()?.to_str()?.to_owned()))
+        {
+            walk_slaves(&disk, resolved, depth + 1);
+        }
+        return;
+    }
+
+    if device_name.starts_with("dm-") {
+        if let Some(dm) = get_device_mapper(device_name) {
+            resolved.device_mappers.push(dm);
+        }
+    } else if device_name.starts_with("md") {
+        resolved.md_arrays.push(device_name.to_owned());
+    }
+
+    let slaves = list_dir_names(class.join("slaves"));
+    if slaves.is_empty() {
+        if !resolved.disks.iter().any(|disk| disk == device_name) {
+            resolved.disks.push(device_name.to_owned());
+        }
+        return;
+    }
+    for slave in slaves {
+        walk_slaves(&slave, resolved, depth + 1);
+    }
+}
+
+/// Return the [MountDevice] backing `path`.
+///
+/// `path` does not need to be a mount point, the mount holding it is used.
+/// The device is followed through partitions, device-mapper (LVM, LUKS, ...)
+/// and md arrays down to the whole disks, which can be matched against [IoBlock].
+///
+/// [MountDevice]: ../disks/struct.MountDevice.html
+/// [IoBlock]: ../disks/struct.IoBlock.html
+pub fn get_mount_device<P>(path: P) -> Result<MountDevice, Error>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref().canonicalize()?;
+    let mounts = get_mounts()?;
+    let mount = match find_mount(&mounts, &path) {
+        Some(mount) => mount.clone(),
+        None => {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                "No mount found for the given path",
+            ))
+        }
+    };
+
+    let (major, minor, sysfs) = match sysfs_dev_path(mount.major, mount.minor) {
+        Some(sysfs) => (mount.major, mount.minor, Some(sysfs)),
+        None => match resolve_from_source(&mount.source) {
+            Some((major, minor, sysfs)) => (major, minor, Some(sysfs)),
+            None => (mount.major, mount.minor, None),
+        },
+    };
+
+    let mut resolved = MountDevice {
+        mount_point: mount.mount_point,
+        major,
+        minor,
+        ..Default::default()
+    };
+    // Virtual filesystems (tmpfs, proc, nfs, ...) have no backing block device
+    let sysfs = match sysfs {
+        Some(sysfs) => sysfs,
+        None => return Ok(resolved),
+    };
+
+    let device_name = sysfs
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.to_owned());
+    if sysfs.join("partition").exists() {
+        resolved.parent_disk = sysfs
+            .parent()
+            .and_then(|parent| parent.file_name())
+            .and_then(|name| name.to_str())
+            .map(|name| name.to_owned());
+    }
+    if let Some(ref name) = device_name {
+        walk_slaves(name, &mut resolved, 0);
+    }
+    resolved.device_name = device_name;
+
+    Ok(resolved)
+}
@@ -0,0 +1,111 @@
+use crate::disks::Mount;
+
+use std::io::{Error, ErrorKind};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+/// Decode the octal escapes (`\040`, `\011`, `\012`, `\134`) the kernel
+/// uses for whitespaces and backslashes in `/proc/self/mountinfo`.
+pub(crate) fn unescape_octal(field: &str) -> String {
+    if !field.contains('\\') {
+        return field.to_owned();
+    }
+
+    let bytes = field.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        // An escape is a backslash followed by exactly 3 octal digits
+        if let (b'\\', Some(&[a @ b'0'..=b'3', b @ b'0'..=b'7', c @ b'0'..=b'7'])) =
+            (bytes[i], bytes.get(i + 1..i + 4))
+        {
+            decoded.push((a - b'0') * 64 + (b - b'0') * 8 + (c - b'0'));
+            i += 4;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[inline]
+fn split_options(options: &str) -> Vec<String> {
+    options
+        .split(',')
+        .filter(|opt| !opt.is_empty())
+        .map(unescape_octal)
+        .collect()
+}
+
+/// Parse a single line of `/proc/self/mountinfo`, which looks like:
+/// `36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue`
+fn parse_mountinfo_line(line: &str) -> Option<Mount> {
+    let mut fields = line.split_whitespace();
+
+    let mount_id = fields.next()?.parse().ok()?;
+    let parent_id = fields.next()?.parse().ok()?;
+    let (major, minor) = fields.next()?.split_once(':')?;
+    let root = unescape_octal(fields.next()?);
+    let mount_point = unescape_octal(fields.next()?);
+    let mount_options = split_options(fields.next()?);
+
+    // Optional fields are terminated by a single hyphen
+    let mut optional_fields = Vec::new();
+    loop {
+        match fields.next()? {
+            "-" => break,
+            field => optional_fields.push(field.to_owned()),
+        }
+    }
+
+    let fstype = unescape_octal(fields.next()?);
+    let source = unescape_octal(fields.next()?);
+    let super_options = fields.next().map_or_else(Vec::new, split_options);
+
+    Some(Mount {
+        mount_id,
+        parent_id,
+        major: major.parse().ok()?,
+        minor: minor.parse().ok()?,
+        root,
+        mount_point,
+        mount_options,
+        optional_fields,
+        fstype,
+        source,
+        super_options,
+    })
+}
+
+/// Parse the content of a mountinfo file (eg: `/proc/<pid>/mountinfo`).
+///
+/// The escaped characters (eg: spaces as `\040`) of the paths and options are decoded.
+pub fn parse_mountinfo<R: BufRead>(mut reader: R) -> Result<Vec<Mount>, Error> {
+    let mut mounts: Vec<Mount> = Vec::new();
+
+    let mut line = String::with_capacity(512);
+    while reader.read_line(&mut line)? != 0 {
+        match parse_mountinfo_line(&line) {
+            Some(mount) => mounts.push(mount),
+            None => return Err(Error::new(ErrorKind::Other, "Invalid mountinfo line")),
+        }
+        line.clear();
+    }
+
+    Ok(mounts)
+}
+
+/// Return a Vec of [Mount] for every mount visible to the current process.
+///
+/// Contrary to `/proc/mounts`, the escaped characters (eg: spaces as `\040`)
+/// of the mount point and source are decoded.
+///
+/// [Mount]: ../disks/struct.Mount.html
+pub fn get_mounts() -> Result<Vec<Mount>, Error> {
+    let file = File::open("/proc/self/mountinfo")?;
+    parse_mountinfo(BufReader::with_capacity(8192, file))
+}
@@ -0,0 +1,159 @@
+use crate::disks::{unescape_octal, NfsByteStats, NfsMountStats, NfsOpStats, NfsTransportStats};
+
+use std::io::Error;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+#[inline]
+fn parse_u64s(values: &str) -> Vec<u64> {
+    values
+        .split_whitespace()
+        .map(|val| val.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Parse the device line: `device srv:/export mounted on /mnt with fstype nfs4 statvers=1.1`.
+fn parse_device_line(line: &str) -> Option<NfsMountStats> {
+    let mut fields = line.split_whitespace();
+    let device = fields.nth(1)?;
+    let mount_point = fields.nth(2)?;
+    let fstype = fields.nth(2)?;
+    if fstype != "nfs" && fstype != "nfs4" {
+        return None;
+    }
+    let device = unescape_octal(device);
+    let (server, export) = match device.split_once(":/") {
+        Some((server, export)) => (server.to_owned(), format!("/{}", export)),
+        None => (device, String::new()),
+    };
+
+    Some(NfsMountStats {
+        server,
+        export,
+        mount_point: unescape_octal(mount_point),
+        fstype: fstype.to_owned(),
+        ..Default::default()
+    })
+}
+
+/// Parse the transport line (after `xprt:`), which differs between protocols:
+/// `tcp port bind_count connect_count connect_time idle_time sends recvs bad_xids ...`
+/// `udp port bind_count sends recvs bad_xids ...`
+fn parse_xprt(values: &str) -> NfsTransportStats {
+    let mut fields = values.split_whitespace();
+    let protocol = fields.next().unwrap_or_default().to_owned();
+    let counters: Vec<u64> = fields.map(|val| val.parse().unwrap_or(0)).collect();
+    let offset = if protocol == "udp" { 2 } else { 5 };
+    let get = |idx: usize| counters.get(offset + idx).copied().unwrap_or(0);
+
+    NfsTransportStats {
+        sends: get(0),
+        recvs: get(1),
+        bad_xids: get(2),
+        protocol,
+    }
+}
+
+/// Parse a per-op line: `READ: ops trans timeouts bytes_sent bytes_recv queue rtt execute [errors]`.
+fn parse_op(name: &str, values: &str) -> NfsOpStats {
+    let counters = parse_u64s(values);
+    let get = |idx: usize| counters.get(idx).copied().unwrap_or(0);
+
+    NfsOpStats {
+        name: name.to_owned(),
+        ops: get(0),
+        transmissions: get(1),
+        major_timeouts: get(2),
+        bytes_sent: get(3),
+        bytes_recv: get(4),
+        queue_time: get(5),
+        rtt: get(6),
+        execute_time: get(7),
+        errors: get(8),
+    }
+}
+
+/// Return a Vec of [NfsMountStats] for every NFS mount, from `/proc/self/mountstats`.
+///
+/// The counters are cumulative since the mount, use [NfsMountStats::latency_since]
+/// to get the average latencies between two samples.
+///
+/// [NfsMountStats]: ../disks/struct.NfsMountStats.html
+/// [NfsMountStats::latency_since]: ../disks/struct.NfsMountStats.html#method.latency_since
+pub fn get_nfs_mountstats() -> Result<Vec<NfsMountStats>, Error> {
+    let file = File::open("/proc/self/mountstats")?;
+    parse_nfs_mountstats(BufReader::with_capacity(8192, file))
+}
+
+/// Parse the content of a mountstats file (eg: `/proc/<pid>/mountstats`), keeping the NFS mounts.
+pub fn parse_nfs_mountstats<R: BufRead>(mut reader: R) -> Result<Vec<NfsMountStats>, Error> {
+    let mut stats: Vec<NfsMountStats> = Vec::new();
+
+    // Whether the lines we're reading belong to a NFS mount
+    let mut in_nfs = false;
+    let mut line = String::with_capacity(256);
+    while reader.read_line(&mut line)? != 0 {
+        if line.starts_with("device ") {
+            match parse_device_line(&line) {
+                Some(mount) => {
+                    stats.push(mount);
+                    in_nfs = true;
+                }
+                None => in_nfs = false,
+            }
+            line.clear();
+            continue;
+        }
+
+        let mount = match stats.last_mut() {
+            Some(mount) if in_nfs => mount,
+            _ => {
+                line.clear();
+                continue;
+            }
+        };
+        if let Some((key, values)) = line.trim().split_once(':') {
+            match key {
+                "opts" => {
+                    mount.options = values.trim().split(',').map(|o| o.to_owned()).collect();
+                    mount.version = mount
+                        .options
+                        .iter()
+                        .find_map(|opt| opt.strip_prefix("vers="))
+                        .unwrap_or_default()
+                        .to_owned();
+                }
+                "age" => mount.age = values.trim().parse().unwrap_or(0),
+                "bytes" => {
+                    let counters = parse_u64s(values);
+                    let get = |idx: usize| counters.get(idx).copied().unwrap_or(0);
+                    mount.bytes = NfsByteStats {
+                        normal_read: get(0),
+                        normal_write: get(1),
+                        direct_read: get(2),
+                        direct_write: get(3),
+                        server_read: get(4),
+                        server_write: get(5),
+                        read_pages: get(6),
+                        write_pages: get(7),
+                    };
+                }
+                "xprt" => mount.transport = parse_xprt(values),
+                // Per-op lines are the only ones with an uppercase key (READ, WRITE, ...)
+                name if !name.is_empty()
+                    && name
+                        .bytes()
+                        .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_') =>
+                {
+                    mount.operations.push(parse_op(name, values));
+                }
+                _ => {}
+            }
+        }
+        line.clear();
+    }
+
+    Ok(stats)
+}
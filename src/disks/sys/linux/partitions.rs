@@ -1,50 +1,78 @@
-use crate::disks::{disk_usage, is_physical_filesys, Disks};
-
-use std::io::Error;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use crate::disks::{
+    classify_mount, get_filesystems, get_mounts, Disks, FilesystemKind, MountEntry, PartitionQuery,
 };
 
-#[inline]
-fn _get_partitions(physical: bool) -> Result<Vec<Disks>, Error> {
-    let file = File::open("/proc/mounts")?;
-    let mut vdisks: Vec<Disks> = Vec::new();
-    let mut file = BufReader::with_capacity(6144, file);
+use std::collections::HashMap;
+use std::io::Error;
+use std::time::Duration;
 
-    let mut line = String::with_capacity(512);
-    while file.read_line(&mut line)? != 0 {
-        let mut fields = line.split_whitespace();
-        let name = fields.next().unwrap();
-        let path = fields.next().unwrap();
-        let filesys = fields.next().unwrap();
-        if physical && !is_physical_filesys(filesys) {
-            line.clear();
-            continue;
-        }
-        let usage: (u64, u64) = disk_usage(path.as_bytes())?;
-        vdisks.push(Disks {
-            name: name.to_owned(),
-            mount_point: path.to_owned(),
-            total_space: usage.0 / (1024 * 1024),
-            avail_space: usage.1 / (1024 * 1024),
-        });
-        line.clear()
-    }
+/// List the mounts from /proc/self/mountinfo along with their kind.
+pub(crate) fn get_mount_entries(
+    overrides: &HashMap<String, FilesystemKind>,
+) -> Result<Vec<MountEntry>, Error> {
+    let mounts = get_mounts()?;
+    let filesystems = get_filesystems()?;
 
-    Ok(vdisks)
+    Ok(mounts
+        .into_iter()
+        .map(|mount| {
+            let kind = classify_mount(&mount, &filesystems, overrides);
+            // Bind mounts share the device, btrfs subvolumes share it too
+            // but differ by their subvolid.
+            let subvol = mount
+                .super_options
+                .iter()
+                .find(|opt| opt.starts_with("subvolid="))
+                .map_or("", |opt| opt.as_str());
+            MountEntry {
+                fs_id: format!("{}:{}:{}", mount.major, mount.minor, subvol),
+                read_only: mount.is_read_only(),
+                source: mount.source,
+                mount_point: mount.mount_point,
+                fstype: mount.fstype,
+                kind,
+            }
+        })
+        .collect())
 }
 
 /// Return a Vec of [Disks] (physical and virtual) with their minimal information.
 ///
+/// A mount whose usage can't be read (eg: EACCES) is still listed, with its `error` set.
+/// See [PartitionQuery] for more control over the listed mounts.
+///
 /// [Disks]: ../disks/struct.Disks.html
+/// [PartitionQuery]: ../disks/struct.PartitionQuery.html
 pub fn get_partitions() -> Result<Vec<Disks>, Error> {
-    _get_partitions(false)
+    PartitionQuery::new().run()
+}
+
+/// Same as [get_partitions] but network and FUSE mounts are probed off-thread,
+/// waiting at most `timeout` for each of them. Those timing out have their `error` set.
+///
+/// [get_partitions]: ../disks/fn.get_partitions.html
+pub fn get_partitions_with_timeout(timeout: Duration) -> Result<Vec<Disks>, Error> {
+    PartitionQuery::new().timeout(timeout).run()
 }
 
 /// Return a Vec of [Disks] (physical) with their minimal information.
 ///
+/// Only the mounts classified as [FilesystemKind::Physical] are kept.
+///
 /// [Disks]: ../disks/struct.Disks.html
+/// [FilesystemKind::Physical]: ../disks/enum.FilesystemKind.html#variant.Physical
 pub fn get_partitions_physical() -> Result<Vec<Disks>, Error> {
-    _get_partitions(true)
+    PartitionQuery::new()
+        .include_kind(FilesystemKind::Physical)
+        .run()
+}
+
+/// Same as [get_partitions_physical] but waiting at most `timeout` for each mount.
+///
+/// [get_partitions_physical]: ../disks/fn.get_partitions_physical.html
+pub fn get_partitions_physical_with_timeout(timeout: Duration) -> Result<Vec<Disks>, Error> {
+    PartitionQuery::new()
+        .include_kind(FilesystemKind::Physical)
+        .timeout(timeout)
+        .run()
 }
@@ -138,6 +138,7 @@ unsafe fn _get_ioblocks(physical: bool) -> Result<Vec<IoBlock>, Error> {
                 write_count,
                 write_bytes,
                 busy_time,
+                is_loop: false,
             })
         };
 
@@ -1,12 +1,19 @@
 use crate::binding::getfsstat64;
-use crate::disks::{disk_usage, is_physical_filesys, Disks};
+use crate::disks::{
+    filesystem_kind_from_name, is_physical_filesys, Disks, FilesystemKind, MountEntry,
+    PartitionQuery,
+};
 use crate::to_str;
 
 use libc::statfs;
+use std::collections::HashMap;
 use std::io::Error;
+use std::time::Duration;
 
-#[inline]
-fn _get_partitions(physical: bool) -> Result<Vec<Disks>, Error> {
+/// List the mounts from getfsstat along with their kind.
+pub(crate) fn get_mount_entries(
+    overrides: &HashMap<String, FilesystemKind>,
+) -> Result<Vec<MountEntry>, Error> {
     let expected_len = unsafe { getfsstat64(std::ptr::null_mut(), 0, 2) };
     let mut mounts: Vec<statfs> = Vec::with_capacity(expected_len as usize);
 
@@ -26,37 +33,64 @@ fn _get_partitions(physical: bool) -> Result<Vec<Disks>, Error> {
         mounts.set_len(result as usize);
     }
 
-    let mut vdisks: Vec<Disks> = Vec::with_capacity(expected_len as usize);
+    let mut entries: Vec<MountEntry> = Vec::with_capacity(expected_len as usize);
     for stat in mounts {
-        if physical && !is_physical_filesys(to_str(stat.f_fstypename.as_ptr())) {
-            continue;
-        }
-        let path = to_str(stat.f_mntonname.as_ptr());
-        let usage: (u64, u64) = match disk_usage(&path.as_bytes()) {
-            Ok(val) => val,
-            Err(x) => return Err(x),
+        let fstype = to_str(stat.f_fstypename.as_ptr());
+        let kind = match overrides
+            .get(fstype)
+            .copied()
+            .or_else(|| filesystem_kind_from_name(fstype))
+        {
+            Some(kind) => kind,
+            None if is_physical_filesys(fstype) => FilesystemKind::Physical,
+            None => FilesystemKind::Pseudo,
         };
-        vdisks.push(Disks {
-            name: to_str(stat.f_mntfromname.as_ptr()).to_owned(),
-            mount_point: path.to_owned(),
-            total_space: usage.0 / (1024 * 1024),
-            avail_space: usage.1 / (1024 * 1024),
+        let source = to_str(stat.f_mntfromname.as_ptr()).to_owned();
+        entries.push(MountEntry {
+            fs_id: source.clone(),
+            source,
+            mount_point: to_str(stat.f_mntonname.as_ptr()).to_owned(),
+            fstype: fstype.to_owned(),
+            kind,
+            read_only: stat.f_flags & libc::MNT_RDONLY as u32 != 0,
         });
     }
 
-    Ok(vdisks)
+    Ok(entries)
 }
 
 /// Return a Vec of [Disks] (physical and virtual) with their minimal information.
 ///
+/// A mount whose usage can't be read (eg: EACCES) is still listed, with its `error` set.
+///
 /// [Disks]: ../disks/struct.Disks.html
 pub fn get_partitions() -> Result<Vec<Disks>, Error> {
-    _get_partitions(false)
+    PartitionQuery::new().run()
+}
+
+/// Same as [get_partitions] but network and FUSE mounts are probed off-thread,
+/// waiting at most `timeout` for each of them. Those timing out have their `error` set.
+///
+/// [get_partitions]: ../disks/fn.get_partitions.html
+pub fn get_partitions_with_timeout(timeout: Duration) -> Result<Vec<Disks>, Error> {
+    PartitionQuery::new().timeout(timeout).run()
 }
 
 /// Return a Vec of [Disks] (physical) with their minimal information.
 ///
 /// [Disks]: ../disks/struct.Disks.html
 pub fn get_partitions_physical() -> Result<Vec<Disks>, Error> {
-    _get_partitions(true)
+    PartitionQuery::new()
+        .include_kind(FilesystemKind::Physical)
+        .run()
+}
+
+/// Same as [get_partitions_physical] but waiting at most `timeout` for each mount.
+///
+/// [get_partitions_physical]: ../disks/fn.get_partitions_physical.html
+pub fn get_partitions_physical_with_timeout(timeout: Duration) -> Result<Vec<Disks>, Error> {
+    PartitionQuery::new()
+        .include_kind(FilesystemKind::Physical)
+        .timeout(timeout)
+        .run()
 }
@@ -26,6 +26,7 @@
 //! let loadavg = get_loadavg().unwrap();
 //! println!("Load average: {:10} {} {}", loadavg.one, loadavg.five, loadavg.fifteen);
 //! ```
+#![allow(clippy::io_other_error)]
 
 #[cfg(target_os = "linux")]
 macro_rules! nth {
@@ -105,6 +106,42 @@ where
     Ok(content.trim().to_owned())
 }
 
+// Whatever the real sector size of the device is, the kernel
+// always expose sizes and offsets of /sys/block in 512 bytes units.
+#[cfg(target_os = "linux")]
+pub(crate) const SYSFS_SECTOR_SIZE: u64 = 512;
+
+/// Read a sysfs/procfs attribute, returning None if it's missing or empty.
+#[cfg(target_os = "linux")]
+#[inline]
+pub(crate) fn read_attr<P>(path: P) -> Option<String>
+where
+    P: AsRef<std::path::Path>,
+{
+    read_and_trim(path).ok().filter(|val| !val.is_empty())
+}
+
+/// Read and parse a sysfs/procfs attribute, returning None if it's missing or invalid.
+#[cfg(target_os = "linux")]
+#[inline]
+pub(crate) fn read_parsed<T, P>(path: P) -> Option<T>
+where
+    T: std::str::FromStr,
+    P: AsRef<std::path::Path>,
+{
+    read_attr(path).and_then(|val| val.parse().ok())
+}
+
+/// Read a sysfs boolean attribute (`0`/`1`), missing ones being false.
+#[cfg(target_os = "linux")]
+#[inline]
+pub(crate) fn read_attr_bool<P>(path: P) -> bool
+where
+    P: AsRef<std::path::Path>,
+{
+    matches!(read_parsed::<u64, _>(path), Some(val) if val != 0)
+}
+
 /// Convert c_char (string in C) to a str in Rust
 #[inline]
 pub(crate) fn to_str<'a>(s: *const c_char) -> &'a str {
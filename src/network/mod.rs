@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
+mod rates;
 mod sys;
 
+pub use rates::*;
 pub use sys::*;
 
 /// Struct containing the IO counters for the network interfaces.
+///
+/// On macOS, the fifo, frame, carrier and compressed counters are always 0.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IoNet {
     pub interface: String,
@@ -12,8 +18,642 @@ pub struct IoNet {
     pub rx_packets: u64,
     pub rx_errs: u64,
     pub rx_drop: u64,
+    pub rx_fifo: u64,
+    pub rx_frame: u64,
+    pub rx_compressed: u64,
+    pub rx_multicast: u64,
     pub tx_bytes: u64,
     pub tx_packets: u64,
     pub tx_errs: u64,
     pub tx_drop: u64,
+    pub tx_fifo: u64,
+    pub tx_colls: u64,
+    pub tx_carrier: u64,
+    pub tx_compressed: u64,
+}
+
+/// Kind of a network interface.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum InterfaceKind {
+    /// Ethernet interfaces backed by a device
+    Ethernet,
+    Loopback,
+    Bridge,
+    Bond,
+    Vlan,
+    Veth,
+    /// Both tun and tap devices
+    Tun,
+    Wireless,
+    /// Anything else, including the stacked devices (macvlan, ipvlan, ...) and
+    /// the virtual ethernet ones (dummy, vxlan, geneve, ...)
+    #[default]
+    Other,
+}
+
+/// Struct containing a network interface's information (from `/sys/class/net`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetInterface {
+    pub name: String,
+    pub kind: InterfaceKind,
+    /// Hardware type (ARPHRD_*, eg: 1 for ethernet, 772 for loopback)
+    pub hw_type: u16,
+    pub mac_address: Option<String>,
+    pub mtu: u32,
+    /// `up`, `down`, `dormant`, `lowerlayerdown`, `unknown`, ...
+    pub operstate: String,
+    /// None if the interface is administratively down
+    pub carrier: Option<bool>,
+    /// Value is in Mb/s, None if unknown
+    pub speed: Option<u64>,
+    /// `full`, `half` or None if unknown
+    pub duplex: Option<String>,
+    pub ifindex: u32,
+    /// ifindex of the underlying interface (differ from ifindex for vlan, veth, ...)
+    pub iflink: u32,
+    pub driver: Option<String>,
+    /// Bridge or bond the interface is enslaved to
+    pub master: Option<String>,
+    pub tx_queue_len: u32,
+    /// Backed by a device (and not a virtual interface)
+    pub is_physical: bool,
+}
+
+/// Struct containing the addresses of a network interface (from `getifaddrs`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InterfaceAddresses {
+    pub interface: String,
+    pub is_up: bool,
+    pub is_running: bool,
+    pub is_loopback: bool,
+    pub is_multicast: bool,
+    pub is_point_to_point: bool,
+    pub ipv4: Vec<Ipv4Address>,
+    pub ipv6: Vec<Ipv6Address>,
+}
+
+/// Struct containing an IPv4 address of an interface.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ipv4Address {
+    pub address: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+    pub prefix_len: u8,
+    pub broadcast: Option<Ipv4Addr>,
+    /// The other end of a point-to-point link
+    pub peer: Option<Ipv4Addr>,
+}
+
+/// Scope of an IPv6 address.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum Ipv6Scope {
+    Global,
+    /// Loopback (`::1`)
+    Host,
+    /// Link-local (`fe80::/10`)
+    Link,
+    /// Deprecated site-local (`fec0::/10`)
+    Site,
+}
+
+/// Struct containing an IPv6 address of an interface.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ipv6Address {
+    pub address: Ipv6Addr,
+    pub prefix_len: u8,
+    /// The other end of a point-to-point link
+    pub peer: Option<Ipv6Addr>,
+    pub scope: Ipv6Scope,
+    /// Privacy extension address (RFC 4941), always false on macOS
+    pub temporary: bool,
+    /// Address whose preferred lifetime expired, always false on macOS
+    pub deprecated: bool,
+}
+
+/// State of a socket (as in `include/net/tcp_states.h`).
+///
+/// UDP sockets are either `Established` (connected) or `Close`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum TcpState {
+    Established,
+    SynSent,
+    SynRecv,
+    FinWait1,
+    FinWait2,
+    TimeWait,
+    Close,
+    CloseWait,
+    LastAck,
+    Listen,
+    Closing,
+    NewSynRecv,
+    #[default]
+    Unknown,
+}
+
+impl TcpState {
+    /// Convert the kernel's state number to a TcpState.
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            1 => TcpState::Established,
+            2 => TcpState::SynSent,
+            3 => TcpState::SynRecv,
+            4 => TcpState::FinWait1,
+            5 => TcpState::FinWait2,
+            6 => TcpState::TimeWait,
+            7 => TcpState::Close,
+            8 => TcpState::CloseWait,
+            9 => TcpState::LastAck,
+            10 => TcpState::Listen,
+            11 => TcpState::Closing,
+            12 => TcpState::NewSynRecv,
+            _ => TcpState::Unknown,
+        }
+    }
+}
+
+/// Struct containing an entry of the TCP/UDP socket tables (from `/proc/net/{tcp,udp}{,6}`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InetSocket {
+    pub local_address: IpAddr,
+    pub local_port: u16,
+    pub remote_address: IpAddr,
+    pub remote_port: u16,
+    pub state: TcpState,
+    /// Value is in bytes
+    pub tx_queue: u64,
+    /// Value is in bytes
+    pub rx_queue: u64,
+    /// Active timer (0: none, 1: retransmit, 2: keepalive, 3: time_wait, 4: zero window probe)
+    pub timer_active: u8,
+    /// Value is in jiffies
+    pub timer_expires: u64,
+    pub retransmits: u64,
+    pub uid: u32,
+    pub inode: u64,
+    /// Dropped datagrams, None for TCP sockets
+    pub drops: Option<u64>,
+}
+
+/// Struct containing the number of sockets in each [TcpState].
+///
+/// [TcpState]: ../network/enum.TcpState.html
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TcpStateSummary {
+    pub established: usize,
+    pub syn_sent: usize,
+    pub syn_recv: usize,
+    pub fin_wait1: usize,
+    pub fin_wait2: usize,
+    pub time_wait: usize,
+    pub close: usize,
+    pub close_wait: usize,
+    pub last_ack: usize,
+    pub listen: usize,
+    pub closing: usize,
+    pub new_syn_recv: usize,
+    pub unknown: usize,
+    pub total: usize,
+}
+
+impl TcpStateSummary {
+    /// Count the sockets per state.
+    pub fn from_sockets(sockets: &[InetSocket]) -> Self {
+        let mut summary = TcpStateSummary::default();
+        for socket in sockets {
+            let counter = match socket.state {
+                TcpState::Established => &mut summary.established,
+                TcpState::SynSent => &mut summary.syn_sent,
+                TcpState::SynRecv => &mut summary.syn_recv,
+                TcpState::FinWait1 => &mut summary.fin_wait1,
+                TcpState::FinWait2 => &mut summary.fin_wait2,
+                TcpState::TimeWait => &mut summary.time_wait,
+                TcpState::Close => &mut summary.close,
+                TcpState::CloseWait => &mut summary.close_wait,
+                TcpState::LastAck => &mut summary.last_ack,
+                TcpState::Listen => &mut summary.listen,
+                TcpState::Closing => &mut summary.closing,
+                TcpState::NewSynRecv => &mut summary.new_syn_recv,
+                TcpState::Unknown => &mut summary.unknown,
+            };
+            *counter += 1;
+            summary.total += 1;
+        }
+        summary
+    }
+}
+
+/// Struct containing the IP (or IPv6) counters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IpCounters {
+    pub in_receives: u64,
+    pub in_hdr_errors: u64,
+    pub in_addr_errors: u64,
+    pub in_unknown_protos: u64,
+    pub in_discards: u64,
+    pub in_delivers: u64,
+    pub forw_datagrams: u64,
+    pub out_requests: u64,
+    pub out_discards: u64,
+    pub out_no_routes: u64,
+    pub reasm_timeout: u64,
+    pub reasm_reqds: u64,
+    pub reasm_oks: u64,
+    pub reasm_fails: u64,
+    pub frag_oks: u64,
+    pub frag_fails: u64,
+    pub frag_creates: u64,
+}
+
+/// Struct containing the ICMP (or ICMPv6) counters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IcmpCounters {
+    pub in_msgs: u64,
+    pub in_errors: u64,
+    pub in_csum_errors: u64,
+    pub in_dest_unreachs: u64,
+    pub in_time_excds: u64,
+    pub out_msgs: u64,
+    pub out_errors: u64,
+    pub out_dest_unreachs: u64,
+    pub out_time_excds: u64,
+}
+
+/// Struct containing the TCP counters (shared by IPv4 and IPv6).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TcpCounters {
+    pub active_opens: u64,
+    pub passive_opens: u64,
+    pub attempt_fails: u64,
+    pub estab_resets: u64,
+    /// Number of connections currently established (a gauge, not a counter)
+    pub curr_estab: u64,
+    pub in_segs: u64,
+    pub out_segs: u64,
+    pub retrans_segs: u64,
+    pub in_errs: u64,
+    pub out_rsts: u64,
+    pub in_csum_errors: u64,
+}
+
+/// Struct containing the UDP (or UDP-Lite) counters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UdpCounters {
+    pub in_datagrams: u64,
+    pub no_ports: u64,
+    pub in_errors: u64,
+    pub out_datagrams: u64,
+    pub rcvbuf_errors: u64,
+    pub sndbuf_errors: u64,
+    pub in_csum_errors: u64,
+    pub ignored_multi: u64,
+    pub mem_errors: u64,
+}
+
+/// Struct containing the most useful TCP extended counters (`TcpExt` of `/proc/net/netstat`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TcpExtCounters {
+    pub syncookies_sent: u64,
+    pub syncookies_recv: u64,
+    pub syncookies_failed: u64,
+    pub prune_called: u64,
+    /// Number of times the accept queue of a listening socket overflowed
+    pub listen_overflows: u64,
+    /// Number of SYNs dropped by listening sockets (includes the overflows)
+    pub listen_drops: u64,
+    pub tcp_timeouts: u64,
+    pub tcp_fast_retrans: u64,
+    pub tcp_slow_start_retrans: u64,
+    pub tcp_lost_retransmit: u64,
+    pub tcp_syn_retrans: u64,
+    pub tcp_backlog_drop: u64,
+    pub tcp_abort_on_timeout: u64,
+    pub tcp_abort_on_memory: u64,
+}
+
+/// Struct containing the most useful IP extended counters (`IpExt` of `/proc/net/netstat`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IpExtCounters {
+    pub in_no_routes: u64,
+    pub in_truncated_pkts: u64,
+    pub in_mcast_pkts: u64,
+    pub out_mcast_pkts: u64,
+    pub in_bcast_pkts: u64,
+    pub out_bcast_pkts: u64,
+    pub in_octets: u64,
+    pub out_octets: u64,
+    pub in_csum_errors: u64,
+}
+
+/// Struct containing the protocol counters (from `/proc/net/snmp`, `/proc/net/snmp6` and `/proc/net/netstat`).
+///
+/// The well-known counters are typed, missing ones being 0. Every counter is
+/// also kept in `sections`, keyed by section (`Tcp`, `TcpExt`, `Ip6`, ...) then by
+/// kernel name, without the protocol prefix for the IPv6 ones (`Ip6InReceives` is
+/// `sections["Ip6"]["InReceives"]`). Those are signed since a few values are
+/// negative (eg: `MaxConn` is -1).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProtocolStats {
+    pub ip: IpCounters,
+    pub icmp: IcmpCounters,
+    pub tcp: TcpCounters,
+    pub udp: UdpCounters,
+    pub udp_lite: UdpCounters,
+    pub ip6: IpCounters,
+    pub icmp6: IcmpCounters,
+    pub udp6: UdpCounters,
+    pub udp_lite6: UdpCounters,
+    pub tcp_ext: TcpExtCounters,
+    pub ip_ext: IpExtCounters,
+    pub sections: HashMap<String, HashMap<String, i64>>,
+}
+
+impl ProtocolStats {
+    /// Number of UDP (IPv4 and IPv6) datagrams dropped because of a full receive buffer.
+    pub fn udp_receive_buffer_errors(&self) -> u64 {
+        self.udp.rcvbuf_errors + self.udp6.rcvbuf_errors
+    }
+}
+
+/// Struct containing a route (from `/proc/net/route` and `/proc/net/ipv6_route`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Route {
+    pub destination: IpAddr,
+    pub prefix_len: u8,
+    /// None for directly connected routes
+    pub gateway: Option<IpAddr>,
+    pub interface: String,
+    pub metric: u32,
+    /// RTF_* flags (`include/uapi/linux/route.h`)
+    pub flags: u32,
+    /// None if not set (always None for IPv6)
+    pub mtu: Option<u32>,
+}
+
+impl Route {
+    /// The route is usable (RTF_UP set and not a reject route).
+    pub fn is_up(&self) -> bool {
+        // RTF_UP and RTF_REJECT
+        self.flags & 0x0001 != 0 && self.flags & 0x0200 == 0
+    }
+
+    /// The route is a default one (`0.0.0.0/0` or `::/0`).
+    pub fn is_default(&self) -> bool {
+        self.prefix_len == 0
+    }
+}
+
+/// Struct containing the default routes of IPv4 and IPv6.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DefaultGateway {
+    pub ipv4: Option<Route>,
+    pub ipv6: Option<Route>,
+}
+
+/// Struct containing an entry of the neighbor tables (ARP for IPv4, NDP for IPv6).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Neighbor {
+    pub address: IpAddr,
+    /// Hardware type (ARPHRD_*, eg: 1 for ethernet)
+    pub hw_type: u16,
+    /// ATF_* flags (`include/uapi/linux/if_arp.h`), derived from the NUD state for IPv6
+    pub flags: u32,
+    /// None while the resolution is incomplete
+    pub mac_address: Option<String>,
+    /// None if no mask (`*`)
+    pub mask: Option<String>,
+    pub interface: String,
+}
+
+impl Neighbor {
+    /// The hardware address is resolved (ATF_COM).
+    pub fn is_complete(&self) -> bool {
+        self.flags & 0x02 != 0
+    }
+
+    /// The entry is static (ATF_PERM).
+    pub fn is_permanent(&self) -> bool {
+        self.flags & 0x04 != 0
+    }
+}
+
+/// Struct containing the usage of a neighbor table and its garbage collector thresholds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NeighborTable {
+    pub entries: u64,
+    /// Below this number of entries, the garbage collector does not run
+    pub gc_thresh1: u64,
+    /// Soft limit, entries above it are collected after 5 seconds
+    pub gc_thresh2: u64,
+    /// Hard limit, new neighbors are dropped above it
+    pub gc_thresh3: u64,
+}
+
+impl NeighborTable {
+    /// Ratio of the entries to the hard limit (gc_thresh3), between 0 and 1.
+    pub fn usage(&self) -> f64 {
+        if self.gc_thresh3 == 0 {
+            return 0.0;
+        }
+        self.entries as f64 / self.gc_thresh3 as f64
+    }
+}
+
+/// Struct containing the IPv4 and IPv6 neighbor tables.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NeighborTables {
+    pub ipv4: NeighborTable,
+    /// None if IPv6 is disabled
+    pub ipv6: Option<NeighborTable>,
+}
+
+/// Struct containing the sockets counters of a protocol (from `/proc/net/sockstat{,6}`).
+///
+/// Counters not reported for a protocol are 0.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SockstatEntry {
+    pub inuse: u64,
+    /// Sockets no longer attached to a process (TCP only)
+    pub orphan: u64,
+    /// Sockets in TIME_WAIT (TCP only)
+    pub tw: u64,
+    pub alloc: u64,
+    /// Value is in pages
+    pub mem: u64,
+    /// Value is in bytes (FRAG only)
+    pub memory: u64,
+}
+
+/// Struct containing the memory limits of a protocol (`tcp_mem`, `udp_mem`).
+///
+/// Values are in pages
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProtocolMemLimits {
+    /// Below this, the kernel does not regulate the memory allocation
+    pub min: u64,
+    /// Above this, the kernel enters memory pressure
+    pub pressure: u64,
+    /// Above this, the allocations fail
+    pub max: u64,
+}
+
+/// Struct containing the netfilter connection tracking table usage.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConntrackUsage {
+    pub count: u64,
+    pub max: u64,
+}
+
+impl ConntrackUsage {
+    /// Ratio of the tracked connections to the table size, between 0 and 1.
+    pub fn usage(&self) -> f64 {
+        if self.max == 0 {
+            return 0.0;
+        }
+        self.count as f64 / self.max as f64
+    }
+}
+
+/// Struct containing the sockets usage and the memory limits of the protocols.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SocketSummary {
+    pub sockets_used: u64,
+    pub tcp: SockstatEntry,
+    pub udp: SockstatEntry,
+    pub udplite: SockstatEntry,
+    pub raw: SockstatEntry,
+    pub frag: SockstatEntry,
+    pub tcp6: SockstatEntry,
+    pub udp6: SockstatEntry,
+    pub udplite6: SockstatEntry,
+    pub raw6: SockstatEntry,
+    pub frag6: SockstatEntry,
+    pub tcp_mem: ProtocolMemLimits,
+    pub udp_mem: ProtocolMemLimits,
+    /// None if the nf_conntrack module is not loaded
+    pub conntrack: Option<ConntrackUsage>,
+}
+
+impl SocketSummary {
+    /// Ratio of the TCP memory (IPv4 and IPv6) to `tcp_mem` max, between 0 and 1.
+    pub fn tcp_mem_usage(&self) -> f64 {
+        if self.tcp_mem.max == 0 {
+            return 0.0;
+        }
+        self.tcp.mem as f64 / self.tcp_mem.max as f64
+    }
+
+    /// Ratio of the UDP memory (IPv4 and IPv6) to `udp_mem` max, between 0 and 1.
+    pub fn udp_mem_usage(&self) -> f64 {
+        if self.udp_mem.max == 0 {
+            return 0.0;
+        }
+        self.udp.mem as f64 / self.udp_mem.max as f64
+    }
+
+    /// TCP is above the memory pressure threshold.
+    pub fn tcp_under_pressure(&self) -> bool {
+        self.tcp_mem.pressure != 0 && self.tcp.mem >= self.tcp_mem.pressure
+    }
+}
+
+/// Struct containing the packet processing counters of a CPU (from `/proc/net/softnet_stat`).
+///
+/// The kernel counters are 32 bits and wrap around.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SoftnetStat {
+    pub cpu: usize,
+    /// Packets processed by the NAPI poll loop
+    pub processed: u64,
+    /// Packets dropped because the backlog queue was full (see `netdev_max_backlog`)
+    pub dropped: u64,
+    /// Times the poll loop ran out of budget with work remaining (see `netdev_budget`)
+    pub time_squeeze: u64,
+    pub cpu_collision: u64,
+    /// Inter-processor interrupts received to process RPS packets
+    pub received_rps: u64,
+    /// Packets dropped by the flow limit
+    pub flow_limit_count: u64,
+    /// Current length of the backlog queue, 0 on kernels older than 5.10
+    pub backlog_len: u64,
+}
+
+impl SoftnetStat {
+    /// Return the increase of the counters since `previous` (the backlog_len is kept as is).
+    pub fn delta(&self, previous: &SoftnetStat) -> SoftnetStat {
+        let diff = |curr: u64, prev: u64| (curr as u32).wrapping_sub(prev as u32) as u64;
+        SoftnetStat {
+            cpu: self.cpu,
+            processed: diff(self.processed, previous.processed),
+            dropped: diff(self.dropped, previous.dropped),
+            time_squeeze: diff(self.time_squeeze, previous.time_squeeze),
+            cpu_collision: diff(self.cpu_collision, previous.cpu_collision),
+            received_rps: diff(self.received_rps, previous.received_rps),
+            flow_limit_count: diff(self.flow_limit_count, previous.flow_limit_count),
+            backlog_len: self.backlog_len,
+        }
+    }
+
+    /// Return the [delta] of each CPU present in both samples.
+    ///
+    /// [delta]: ../network/struct.SoftnetStat.html#method.delta
+    pub fn deltas(previous: &[SoftnetStat], current: &[SoftnetStat]) -> Vec<SoftnetStat> {
+        current
+            .iter()
+            .filter_map(|curr| {
+                let prev = previous.iter().find(|prev| prev.cpu == curr.cpu)?;
+                Some(curr.delta(prev))
+            })
+            .collect()
+    }
+}
+
+/// Struct containing the link quality of a wireless interface (from `/proc/net/wireless`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WirelessStats {
+    pub interface: String,
+    /// Driver specific status
+    pub status: u16,
+    /// Driver specific scale (often out of 70)
+    pub link_quality: f64,
+    /// Value is in dBm (for most drivers)
+    pub signal_level: f64,
+    /// None if the driver does not report it. Value is in dBm (for most drivers)
+    pub noise_level: Option<f64>,
+    /// Packets discarded because of another network id
+    pub discarded_nwid: u64,
+    /// Packets discarded because they could not be decrypted
+    pub discarded_crypt: u64,
+    /// Packets discarded because they could not be reassembled
+    pub discarded_frag: u64,
+    /// Packets discarded after reaching the max MAC retries
+    pub discarded_retry: u64,
+    pub discarded_misc: u64,
+    pub missed_beacon: u64,
+}
+
+/// Protocol of a listening socket.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum SocketProtocol {
+    Tcp,
+    Udp,
+    Unix,
+}
+
+/// Struct containing a listening socket and the process owning it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListeningPort {
+    pub protocol: SocketProtocol,
+    /// None for UNIX sockets
+    pub address: Option<IpAddr>,
+    /// 0 for UNIX sockets
+    pub port: u16,
+    /// Path of UNIX sockets, abstract ones start with `@`
+    pub path: Option<String>,
+    pub inode: u64,
+    /// None if the owner could not be found (not enough permissions to read its fds)
+    pub pid: Option<u32>,
+    pub process_name: Option<String>,
+    /// For UNIX sockets, uid of the owning process (None if the owner is unknown)
+    pub uid: Option<u32>,
+    pub user: Option<String>,
 }
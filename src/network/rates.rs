@@ -0,0 +1,202 @@
+use crate::network::{interface_index, IoNet};
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Struct containing the per-second rates of a network interface.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IoNetRate {
+    pub interface: String,
+    /// Value is in bytes/s
+    pub rx_bytes: f64,
+    /// Value is in bytes/s
+    pub tx_bytes: f64,
+    pub rx_packets: f64,
+    pub tx_packets: f64,
+    pub rx_errs: f64,
+    pub tx_errs: f64,
+    pub rx_drop: f64,
+    pub tx_drop: f64,
+    /// Time elapsed since the previous sample. Value is in seconds
+    pub interval: f64,
+    /// The counters were reset (interface re-created, driver reloaded, ...),
+    /// the rates are 0 for this sample
+    pub reset: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Previous {
+    ifindex: Option<u32>,
+    time: Instant,
+    counters: [u64; 8],
+    /// Per-second rates of the previous sample
+    rates: [f64; 8],
+    /// The counter went above u32::MAX, so it can't be a wrapping 32 bits one
+    wide: [bool; 8],
+}
+
+/// Compute the per-second rates of the network interfaces from successive [IoNet] samples.
+///
+/// Handle the 32 bits counters wrapping, the counters reset (eg: a veth
+/// re-created by a container restart) and the interfaces renamed.
+///
+/// ```ignore
+/// use sys_metrics::network::*;
+///
+/// let mut sampler = IoNetSampler::new();
+/// loop {
+///     for rate in sampler.sample(&get_ionets().unwrap()) {
+///         println!("{}: {} B/s in", rate.interface, rate.rx_bytes);
+///     }
+///     std::thread::sleep(std::time::Duration::from_secs(1));
+/// }
+/// ```
+///
+/// [IoNet]: ../network/struct.IoNet.html
+#[derive(Debug, Clone, Default)]
+pub struct IoNetSampler {
+    previous: HashMap<String, Previous>,
+}
+
+#[inline]
+fn counters(ionet: &IoNet) -> [u64; 8] {
+    [
+        ionet.rx_bytes,
+        ionet.tx_bytes,
+        ionet.rx_packets,
+        ionet.tx_packets,
+        ionet.rx_errs,
+        ionet.tx_errs,
+        ionet.rx_drop,
+        ionet.tx_drop,
+    ]
+}
+
+// Below this, a 32 bits wrap is always considered plausible (eg: after an idle period)
+const MIN_PLAUSIBLE_WRAP: u64 = (u32::MAX / 16) as u64;
+
+/// Increase of a counter, None if it went backward for another reason than a 32 bits wrap.
+///
+/// A wrap is only considered for counters which never went above u32::MAX, and if
+/// the increase is plausible given the previous rate (at most twice as fast).
+#[inline]
+fn delta(prev: u64, curr: u64, wide: bool, prev_rate: f64, interval: f64) -> Option<u64> {
+    if curr >= prev {
+        return Some(curr - prev);
+    }
+    if wide || prev > u32::MAX as u64 {
+        return None;
+    }
+
+    // A 32 bits counter from an old driver wrapped around
+    let wrapped = curr + (u32::MAX as u64 + 1 - prev);
+    if wrapped <= MIN_PLAUSIBLE_WRAP || wrapped as f64 <= 2.0 * prev_rate * interval {
+        Some(wrapped)
+    } else {
+        None
+    }
+}
+
+impl IoNetSampler {
+    /// Create an empty sampler.
+    pub fn new() -> Self {
+        IoNetSampler::default()
+    }
+
+    /// Add a sample taken now, see [sample_at].
+    ///
+    /// [sample_at]: ../network/struct.IoNetSampler.html#method.sample_at
+    pub fn sample(&mut self, ionets: &[IoNet]) -> Vec<IoNetRate> {
+        self.sample_at(Instant::now(), ionets)
+    }
+
+    /// Add a sample taken at `time` and return the rates since the previous one.
+    ///
+    /// Interfaces seen for the first time have no rate, interfaces which
+    /// disappeared are forgotten.
+    pub fn sample_at(&mut self, time: Instant, ionets: &[IoNet]) -> Vec<IoNetRate> {
+        let mut previous = std::mem::take(&mut self.previous);
+        let mut rates = Vec::with_capacity(ionets.len());
+
+        for ionet in ionets {
+            let ifindex = interface_index(&ionet.interface);
+            let curr = counters(ionet);
+
+            // An interface renamed keeps its index (and its counters)
+            let prev = match previous.remove(&ionet.interface) {
+                Some(prev) => Some(prev),
+                None => ifindex.and_then(|index| {
+                    let name = previous
+                        .iter()
+                        .find(|(_, prev)| prev.ifindex == Some(index))
+                        .map(|(name, _)| name.clone())?;
+                    previous.remove(&name)
+                }),
+            };
+
+            let mut wide = [false; 8];
+            let mut last_rates = [0.0; 8];
+            if let Some(prev) = prev {
+                let interval = time.saturating_duration_since(prev.time).as_secs_f64();
+                // Same name but another index: the interface was re-created
+                let recreated = matches!((prev.ifindex, ifindex), (Some(a), Some(b)) if a != b);
+                let deltas: Option<Vec<u64>> = (0..8)
+                    .map(|i| {
+                        delta(
+                            prev.counters[i],
+                            curr[i],
+                            prev.wide[i],
+                            prev.rates[i],
+                            interval,
+                        )
+                    })
+                    .collect();
+
+                let mut rate = IoNetRate {
+                    interface: ionet.interface.clone(),
+                    interval,
+                    ..Default::default()
+                };
+                match deltas {
+                    Some(deltas) if !recreated && interval > 0.0 => {
+                        for (last_rate, delta) in last_rates.iter_mut().zip(deltas.iter()) {
+                            *last_rate = *delta as f64 / interval;
+                        }
+                        rate.rx_bytes = last_rates[0];
+                        rate.tx_bytes = last_rates[1];
+                        rate.rx_packets = last_rates[2];
+                        rate.tx_packets = last_rates[3];
+                        rate.rx_errs = last_rates[4];
+                        rate.tx_errs = last_rates[5];
+                        rate.rx_drop = last_rates[6];
+                        rate.tx_drop = last_rates[7];
+                    }
+                    Some(_) if !recreated => last_rates = prev.rates,
+                    _ => rate.reset = true,
+                }
+                // A re-created interface may come with another driver
+                if !recreated {
+                    wide = prev.wide;
+                }
+                rates.push(rate);
+            }
+            for (wide, value) in wide.iter_mut().zip(curr.iter()) {
+                *wide |= *value > u32::MAX as u64;
+            }
+
+            self.previous.insert(
+                ionet.interface.clone(),
+                Previous {
+                    ifindex,
+                    time,
+                    counters: curr,
+                    rates: last_rates,
+                    wide,
+                },
+            );
+        }
+
+        rates
+    }
+}
@@ -0,0 +1,124 @@
+use crate::network::{InterfaceKind, NetInterface};
+use crate::{read_attr, read_parsed};
+
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+
+// ARPHRD_* values from include/uapi/linux/if_arp.h
+const ARPHRD_ETHER: u16 = 1;
+const ARPHRD_LOOPBACK: u16 = 772;
+
+/// Name of the target of a symlink (eg: `device/driver`, `master`).
+#[inline]
+fn link_name<P: AsRef<Path>>(path: P) -> Option<String> {
+    fs::read_link(path)
+        .ok()
+        .and_then(|target| Some(target.file_name()?.to_str()?.to_owned()))
+}
+
+/// Tell if the interface is backed by a device, virtual ones only live in /sys/devices/virtual.
+#[inline]
+pub(crate) fn is_physical_interface(name: &str) -> bool {
+    Path::new("/sys/class/net")
+        .join(name)
+        .join("device")
+        .exists()
+}
+
+/// Tell if the interface is a wireless one (it has a `wireless` or `phy80211` entry in `/sys/class/net`).
+#[inline]
+pub fn is_wireless_interface(name: &str) -> bool {
+    let path = Path::new("/sys/class/net").join(name);
+    path.join("wireless").exists() || path.join("phy80211").exists()
+}
+
+/// Tell if the interface is stacked on top of another one (`lower_<name>` links).
+#[inline]
+fn has_lower_device(path: &Path) -> bool {
+    fs::read_dir(path).is_ok_and(|entries| {
+        entries
+            .filter_map(|entry| entry.ok())
+            .any(|entry| entry.file_name().to_string_lossy().starts_with("lower_"))
+    })
+}
+
+fn get_kind(path: &Path, name: &str, hw_type: u16, ifindex: u32, iflink: u32) -> InterfaceKind {
+    let devtype = read_attr(path.join("uevent")).and_then(|uevent| {
+        uevent
+            .lines()
+            .find_map(|line| line.strip_prefix("DEVTYPE="))
+            .map(|devtype| devtype.to_owned())
+    });
+
+    if hw_type == ARPHRD_LOOPBACK {
+        InterfaceKind::Loopback
+    } else if path.join("bridge").exists() {
+        InterfaceKind::Bridge
+    } else if path.join("bonding").exists() {
+        InterfaceKind::Bond
+    } else if is_wireless_interface(name) || devtype.as_deref() == Some("wlan") {
+        InterfaceKind::Wireless
+    } else if path.join("tun_flags").exists() {
+        InterfaceKind::Tun
+    } else if devtype.as_deref() == Some("vlan") || Path::new("/proc/net/vlan").join(name).exists()
+    {
+        InterfaceKind::Vlan
+    } else if hw_type == ARPHRD_ETHER && is_physical_interface(name) {
+        InterfaceKind::Ethernet
+    } else if hw_type == ARPHRD_ETHER
+        && devtype.is_none()
+        && iflink != ifindex
+        && !has_lower_device(path)
+    {
+        // A virtual ethernet linked to another interface: its peer for a veth. Stacked
+        // devices (macvlan, ipvlan, ...) are linked to their parent, exposed as lower_<parent>,
+        // and tunnels (vxlan, geneve) to their underlay, but they report a DEVTYPE.
+        InterfaceKind::Veth
+    } else {
+        // Including the virtual ethernet devices without a peer (dummy, vxlan, geneve, ...)
+        InterfaceKind::Other
+    }
+}
+
+fn get_interface(name: String) -> NetInterface {
+    let path = Path::new("/sys/class/net").join(&name);
+    let hw_type = read_parsed(path.join("type")).unwrap_or(0);
+    let ifindex = read_parsed(path.join("ifindex")).unwrap_or(0);
+    let iflink = read_parsed(path.join("iflink")).unwrap_or(ifindex);
+
+    NetInterface {
+        kind: get_kind(&path, &name, hw_type, ifindex, iflink),
+        hw_type,
+        mac_address: read_attr(path.join("address")).filter(|addr| !addr.is_empty()),
+        mtu: read_parsed(path.join("mtu")).unwrap_or(0),
+        operstate: read_attr(path.join("operstate")).unwrap_or_default(),
+        // Reading carrier fails with EINVAL when the interface is down
+        carrier: read_parsed::<u8, _>(path.join("carrier")).map(|carrier| carrier != 0),
+        // Reported as -1 when the link is down or the driver doesn't know
+        speed: read_parsed::<i64, _>(path.join("speed"))
+            .filter(|speed| *speed > 0)
+            .map(|speed| speed as u64),
+        duplex: read_attr(path.join("duplex")).filter(|duplex| duplex != "unknown"),
+        ifindex,
+        iflink,
+        driver: link_name(path.join("device/driver")),
+        master: link_name(path.join("master")),
+        tx_queue_len: read_parsed(path.join("tx_queue_len")).unwrap_or(0),
+        is_physical: is_physical_interface(&name),
+        name,
+    }
+}
+
+/// Return a Vec of [NetInterface] for every network interface of the host.
+///
+/// [NetInterface]: ../network/struct.NetInterface.html
+pub fn get_interfaces() -> Result<Vec<NetInterface>, Error> {
+    let mut names: Vec<String> = fs::read_dir("/sys/class/net")?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+
+    Ok(names.into_iter().map(get_interface).collect())
+}
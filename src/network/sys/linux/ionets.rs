@@ -1,9 +1,8 @@
-use crate::network::IoNet;
+use crate::network::{is_physical_interface, IoNet};
 
 use std::{
     fs::File,
     io::{BufRead, BufReader, Error, ErrorKind},
-    path::Path,
 };
 
 #[inline]
@@ -29,13 +28,13 @@ fn _get_ionets(physical: bool) -> Result<Vec<IoNet>, Error> {
             }
         };
 
-        if physical && Path::new(&format!("/sys/devices/virtual/net/{}", interface)).exists() {
+        if physical && !is_physical_interface(interface) {
             line.clear();
             continue;
         }
 
         let interface = interface.to_owned();
-        let mut parts = match parts.next() {
+        let parts = match parts.next() {
             Some(rest) => rest.split_whitespace(),
             None => {
                 return Err(Error::new(
@@ -45,26 +44,38 @@ fn _get_ionets(physical: bool) -> Result<Vec<IoNet>, Error> {
             }
         };
 
-        let rx_bytes = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let rx_packets = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let rx_errs = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let rx_drop = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let mut parts = parts.skip(4);
-        let tx_bytes = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let tx_packets = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let tx_errs = parts.next().unwrap_or("0").parse::<u64>().unwrap();
-        let tx_drop = parts.next().unwrap_or("0").parse::<u64>().unwrap();
+        // The 16 counters (8 for rx, 8 for tx), missing ones are left to 0
+        let mut counters = [0u64; 16];
+        for (counter, value) in counters.iter_mut().zip(parts) {
+            *counter = match value.parse::<u64>() {
+                Ok(val) => val,
+                Err(_) => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Invalid counter in /proc/net/dev for {}", interface),
+                    ));
+                }
+            };
+        }
 
         v_ionets.push(IoNet {
             interface,
-            rx_bytes,
-            rx_packets,
-            rx_errs,
-            rx_drop,
-            tx_bytes,
-            tx_packets,
-            tx_errs,
-            tx_drop,
+            rx_bytes: counters[0],
+            rx_packets: counters[1],
+            rx_errs: counters[2],
+            rx_drop: counters[3],
+            rx_fifo: counters[4],
+            rx_frame: counters[5],
+            rx_compressed: counters[6],
+            rx_multicast: counters[7],
+            tx_bytes: counters[8],
+            tx_packets: counters[9],
+            tx_errs: counters[10],
+            tx_drop: counters[11],
+            tx_fifo: counters[12],
+            tx_colls: counters[13],
+            tx_carrier: counters[14],
+            tx_compressed: counters[15],
         });
         line.clear();
     }
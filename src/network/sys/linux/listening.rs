@@ -0,0 +1,185 @@
+use crate::network::{get_tcp_sockets, get_udp_sockets, ListeningPort, SocketProtocol, TcpState};
+use crate::read_and_trim;
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufRead, BufReader, Error, ErrorKind},
+};
+
+// __SO_ACCEPTCON from include/linux/net.h
+const SO_ACCEPTCON: u32 = 0x0001_0000;
+
+/// Map each socket inode to the (lowest) pid having it open.
+///
+/// Processes whose fds can't be read (permissions, exited meanwhile) are skipped.
+fn get_socket_owners() -> Result<HashMap<u64, u32>, Error> {
+    let mut owners: HashMap<u64, u32> = HashMap::new();
+
+    for entry in fs::read_dir("/proc")?.flatten() {
+        let pid = match entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        {
+            Some(pid) => pid,
+            None => continue,
+        };
+        let fds = match fs::read_dir(entry.path().join("fd")) {
+            Ok(fds) => fds,
+            Err(_) => continue,
+        };
+
+        for fd in fds.flatten() {
+            // Socket fds link to `socket:[inode]`
+            let inode = fs::read_link(fd.path()).ok().and_then(|target| {
+                target
+                    .to_str()?
+                    .strip_prefix("socket:[")?
+                    .strip_suffix(']')?
+                    .parse::<u64>()
+                    .ok()
+            });
+            if let Some(inode) = inode {
+                // Forked workers share the socket of their parent, keep the lowest pid
+                let owner = owners.entry(inode).or_insert(pid);
+                *owner = (*owner).min(pid);
+            }
+        }
+    }
+
+    Ok(owners)
+}
+
+/// Map the uids to the user names from /etc/passwd.
+fn get_user_names() -> HashMap<u32, String> {
+    let content = fs::read_to_string("/etc/passwd").unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| {
+            // username:x:uid:rest
+            let mut parts = line.splitn(4, ':');
+            let name = parts.next()?;
+            let uid = parts.nth(1)?.parse().ok()?;
+            Some((uid, name.to_owned()))
+        })
+        .collect()
+}
+
+/// Return the (inode, path) of the listening UNIX sockets from `/proc/net/unix`.
+fn get_unix_listeners() -> Result<Vec<(u64, Option<String>)>, Error> {
+    let file = File::open("/proc/net/unix")?;
+    let mut file = BufReader::with_capacity(8192, file);
+    let mut listeners = Vec::new();
+
+    let mut header = true;
+    let mut line = String::with_capacity(128);
+    while file.read_line(&mut line)? != 0 {
+        if header {
+            header = false;
+            line.clear();
+            continue;
+        }
+        // Num RefCount Protocol Flags Type St Inode [Path], the path may contain spaces
+        let mut rest = line.trim_end();
+        let mut fields = Vec::with_capacity(7);
+        while fields.len() < 7 {
+            let (field, tail) = rest
+                .trim_start()
+                .split_once(char::is_whitespace)
+                .unwrap_or((rest.trim_start(), ""));
+            fields.push(field);
+            rest = tail;
+        }
+        let path = Some(rest.trim_start()).filter(|path| !path.is_empty());
+        let flags = u32::from_str_radix(fields[3], 16).ok();
+        let inode = fields[6].parse::<u64>().ok();
+        match (flags, inode) {
+            (Some(flags), Some(inode)) => {
+                if flags & SO_ACCEPTCON != 0 {
+                    listeners.push((inode, path.map(|path| path.to_owned())));
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Invalid entry in /proc/net/unix",
+                ));
+            }
+        }
+        line.clear();
+    }
+
+    Ok(listeners)
+}
+
+/// Return a Vec of [ListeningPort] for the TCP listeners and the unconnected UDP sockets,
+/// and optionally the listening UNIX sockets.
+///
+/// Finding the owner of the sockets of other users requires root (or CAP_SYS_PTRACE).
+///
+/// [ListeningPort]: ../network/struct.ListeningPort.html
+pub fn get_listening_ports(include_unix: bool) -> Result<Vec<ListeningPort>, Error> {
+    let owners = get_socket_owners()?;
+    let users = get_user_names();
+    let process_name =
+        |pid: Option<u32>| pid.and_then(|pid| read_and_trim(format!("/proc/{}/comm", pid)).ok());
+
+    let tcp = get_tcp_sockets()?
+        .into_iter()
+        .filter(|socket| socket.state == TcpState::Listen)
+        .map(|socket| (SocketProtocol::Tcp, socket));
+    // UDP has no listen state, a socket bound but not connected receives from anyone
+    let udp = get_udp_sockets()?
+        .into_iter()
+        .filter(|socket| socket.state == TcpState::Close)
+        .map(|socket| (SocketProtocol::Udp, socket));
+
+    let mut ports: Vec<ListeningPort> = tcp
+        .chain(udp)
+        .map(|(protocol, socket)| {
+            let pid = owners.get(&socket.inode).copied();
+            ListeningPort {
+                protocol,
+                address: Some(socket.local_address),
+                port: socket.local_port,
+                path: None,
+                inode: socket.inode,
+                pid,
+                process_name: process_name(pid),
+                uid: Some(socket.uid),
+                user: users.get(&socket.uid).cloned(),
+            }
+        })
+        .collect();
+
+    if include_unix {
+        for (inode, path) in get_unix_listeners()? {
+            let pid = owners.get(&inode).copied();
+            // The kernel does not report the owner uid of UNIX sockets, use the process' one
+            let uid = pid.and_then(|pid| {
+                fs::read_to_string(format!("/proc/{}/status", pid))
+                    .ok()?
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Uid:"))?
+                    .split_whitespace()
+                    .next()?
+                    .parse::<u32>()
+                    .ok()
+            });
+            ports.push(ListeningPort {
+                protocol: SocketProtocol::Unix,
+                address: None,
+                port: 0,
+                path,
+                inode,
+                pid,
+                process_name: process_name(pid),
+                uid,
+                user: uid.and_then(|uid| users.get(&uid).cloned()),
+            });
+        }
+    }
+
+    Ok(ports)
+}
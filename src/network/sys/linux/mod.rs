@@ -1,3 +1,21 @@
+mod interfaces;
 mod ionets;
+mod listening;
+mod neighbors;
+mod protocols;
+mod routes;
+mod sockets;
+mod sockstat;
+mod softnet;
+mod wireless;
 
+pub use interfaces::*;
 pub use ionets::*;
+pub use listening::*;
+pub use neighbors::*;
+pub use protocols::*;
+pub use routes::*;
+pub use sockets::*;
+pub use sockstat::*;
+pub use softnet::*;
+pub use wireless::*;
@@ -0,0 +1,316 @@
+use crate::network::{Neighbor, NeighborTable, NeighborTables};
+use crate::{read_and_trim, read_parsed};
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader, Error, ErrorKind},
+    mem,
+    net::{IpAddr, Ipv6Addr},
+    ptr,
+};
+
+// ATF_* from include/uapi/linux/if_arp.h
+const ATF_COM: u32 = 0x02;
+const ATF_PERM: u32 = 0x04;
+
+/// Struct ndmsg from include/uapi/linux/neighbour.h
+#[repr(C)]
+struct NdMsg {
+    ndm_family: u8,
+    ndm_pad1: u8,
+    ndm_pad2: u16,
+    ndm_ifindex: i32,
+    ndm_state: u16,
+    ndm_flags: u8,
+    ndm_type: u8,
+}
+
+#[repr(C)]
+struct NeighRequest {
+    header: libc::nlmsghdr,
+    msg: NdMsg,
+}
+
+#[inline]
+fn nlmsg_align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Format a hardware address as `aa:bb:cc:dd:ee:ff`.
+#[inline]
+fn format_mac(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[inline]
+fn interface_name(ifindex: u32) -> Option<String> {
+    let mut name = [0 as libc::c_char; libc::IF_NAMESIZE];
+    if unsafe { libc::if_indextoname(ifindex, name.as_mut_ptr()) }.is_null() {
+        return None;
+    }
+    Some(crate::to_str(name.as_ptr()).to_owned())
+}
+
+/// Parse a line of `/proc/net/arp` (without the header).
+fn parse_arp_line(line: &str) -> Option<Neighbor> {
+    // IP address, HW type, Flags, HW address, Mask, Device
+    let mut fields = line.split_whitespace();
+    let parse_hex = |value: &str| u32::from_str_radix(value.trim_start_matches("0x"), 16).ok();
+    let address = fields.next()?.parse().ok()?;
+    let hw_type = parse_hex(fields.next()?)? as u16;
+    let flags = parse_hex(fields.next()?)?;
+    let mac = fields.next()?;
+    let mask = fields.next()?;
+
+    Some(Neighbor {
+        address: IpAddr::V4(address),
+        hw_type,
+        flags,
+        mac_address: Some(mac.to_owned()).filter(|mac| mac != "00:00:00:00:00:00"),
+        mask: Some(mask.to_owned()).filter(|mask| mask != "*"),
+        interface: fields.next()?.to_owned(),
+    })
+}
+
+/// Parse the `/proc/net/arp` table.
+fn get_arp_neighbors() -> Result<Vec<Neighbor>, Error> {
+    let file = File::open("/proc/net/arp")?;
+    let mut file = BufReader::with_capacity(4096, file);
+    let mut neighbors = Vec::new();
+
+    let mut header = true;
+    let mut line = String::with_capacity(128);
+    while file.read_line(&mut line)? != 0 {
+        if header {
+            header = false;
+            line.clear();
+            continue;
+        }
+        match parse_arp_line(&line) {
+            Some(neighbor) => neighbors.push(neighbor),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Invalid entry in /proc/net/arp",
+                ))
+            }
+        }
+        line.clear();
+    }
+
+    Ok(neighbors)
+}
+
+/// Dump the IPv6 neighbors with a RTM_GETNEIGH netlink request (there is no procfs file for them).
+fn get_ndp_neighbors() -> Result<Vec<Neighbor>, Error> {
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+            libc::NETLINK_ROUTE,
+        )
+    };
+    if fd < 0 {
+        return Err(Error::last_os_error());
+    }
+
+    let result = unsafe { dump_ndp_neighbors(fd) };
+    unsafe { libc::close(fd) };
+    result
+}
+
+unsafe fn dump_ndp_neighbors(fd: libc::c_int) -> Result<Vec<Neighbor>, Error> {
+    let mut request: NeighRequest = mem::zeroed();
+    request.header.nlmsg_len = mem::size_of::<NeighRequest>() as u32;
+    request.header.nlmsg_type = libc::RTM_GETNEIGH;
+    request.header.nlmsg_flags = (libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16;
+    request.header.nlmsg_seq = 1;
+    request.msg.ndm_family = libc::AF_INET6 as u8;
+
+    let mut addr: libc::sockaddr_nl = mem::zeroed();
+    addr.nl_family = libc::AF_NETLINK as u16;
+    if libc::sendto(
+        fd,
+        &request as *const _ as *const libc::c_void,
+        mem::size_of::<NeighRequest>(),
+        0,
+        &addr as *const _ as *const libc::sockaddr,
+        mem::size_of::<libc::sockaddr_nl>() as u32,
+    ) < 0
+    {
+        return Err(Error::last_os_error());
+    }
+
+    let header_len = nlmsg_align(mem::size_of::<libc::nlmsghdr>());
+    let ndmsg_len = nlmsg_align(mem::size_of::<NdMsg>());
+    let mut neighbors = Vec::new();
+    // Name and hardware type of the interfaces, by ifindex
+    let mut interfaces: HashMap<i32, (String, u16)> = HashMap::new();
+    let mut buf = vec![0u8; 32768];
+    loop {
+        let len = libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0);
+        if len < 0 {
+            return Err(Error::last_os_error());
+        }
+        let data = &buf[..len as usize];
+
+        let mut offset = 0;
+        while offset + header_len <= data.len() {
+            // The buffer is only byte aligned, so copy the header out of it
+            let header = ptr::read_unaligned(data[offset..].as_ptr() as *const libc::nlmsghdr);
+            let msg_len = header.nlmsg_len as usize;
+            if msg_len < header_len || offset + msg_len > data.len() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Truncated netlink message",
+                ));
+            }
+            match header.nlmsg_type as libc::c_int {
+                libc::NLMSG_DONE => return Ok(neighbors),
+                libc::NLMSG_ERROR => {
+                    // struct nlmsgerr starts with the (negative) errno
+                    let errno = data
+                        .get(offset + header_len..offset + header_len + 4)
+                        .map_or(0, |errno| {
+                            i32::from_ne_bytes([errno[0], errno[1], errno[2], errno[3]])
+                        });
+                    return Err(Error::from_raw_os_error(-errno));
+                }
+                _ if header.nlmsg_type == libc::RTM_NEWNEIGH
+                    && msg_len >= header_len + ndmsg_len =>
+                {
+                    let payload = &data[offset + header_len..offset + msg_len];
+                    if let Some(neighbor) = parse_ndmsg(payload, ndmsg_len, &mut interfaces) {
+                        neighbors.push(neighbor);
+                    }
+                }
+                _ => {}
+            }
+            offset += nlmsg_align(msg_len);
+        }
+    }
+}
+
+/// Parse a ndmsg and its attributes (NDA_DST and NDA_LLADDR).
+fn parse_ndmsg(
+    payload: &[u8],
+    ndmsg_len: usize,
+    interfaces: &mut HashMap<i32, (String, u16)>,
+) -> Option<Neighbor> {
+    if payload.len() < mem::size_of::<NdMsg>() {
+        return None;
+    }
+    let msg = unsafe { ptr::read_unaligned(payload.as_ptr() as *const NdMsg) };
+
+    let mut address = None;
+    let mut mac_address = None;
+    let mut offset = ndmsg_len;
+    // struct rtattr is { u16 rta_len, u16 rta_type }
+    while offset + 4 <= payload.len() {
+        let rta_len = u16::from_ne_bytes([payload[offset], payload[offset + 1]]) as usize;
+        let rta_type = u16::from_ne_bytes([payload[offset + 2], payload[offset + 3]]);
+        if rta_len < 4 || offset + rta_len > payload.len() {
+            break;
+        }
+        let value = &payload[offset + 4..offset + rta_len];
+        match rta_type {
+            libc::NDA_DST if value.len() == 16 => {
+                let mut bytes = [0u8; 16];
+                bytes.copy_from_slice(value);
+                address = Some(Ipv6Addr::from(bytes));
+            }
+            libc::NDA_LLADDR if !value.is_empty() => mac_address = Some(format_mac(value)),
+            _ => {}
+        }
+        offset += nlmsg_align(rta_len);
+    }
+
+    let state = msg.ndm_state;
+    let mut flags = 0;
+    if state & libc::NUD_PERMANENT != 0 {
+        flags |= ATF_COM | ATF_PERM;
+    } else if state
+        & (libc::NUD_REACHABLE
+            | libc::NUD_STALE
+            | libc::NUD_DELAY
+            | libc::NUD_PROBE
+            | libc::NUD_NOARP)
+        != 0
+    {
+        flags |= ATF_COM;
+    }
+    let (interface, hw_type) = interfaces
+        .entry(msg.ndm_ifindex)
+        .or_insert_with(|| {
+            let name = interface_name(msg.ndm_ifindex as u32).unwrap_or_default();
+            let hw_type = read_parsed(format!("/sys/class/net/{}/type", name)).unwrap_or(0);
+            (name, hw_type)
+        })
+        .clone();
+
+    Some(Neighbor {
+        address: IpAddr::V6(address?),
+        hw_type,
+        flags,
+        mac_address,
+        mask: None,
+        interface,
+    })
+}
+
+/// Return a Vec of [Neighbor] from the ARP table and, when available, the IPv6 neighbor table.
+///
+/// [Neighbor]: ../network/struct.Neighbor.html
+pub fn get_neighbors() -> Result<Vec<Neighbor>, Error> {
+    let mut neighbors = get_arp_neighbors()?;
+    // Netlink may be unavailable (IPv6 disabled, seccomp, ...), the ARP table is still useful
+    if let Ok(ndp) = get_ndp_neighbors() {
+        neighbors.extend(ndp);
+    }
+    Ok(neighbors)
+}
+
+/// Read the usage of a neighbor table (`arp_cache` or `ndisc_cache`) and its gc_thresh.
+fn get_neighbor_table(cache: &str, family: &str) -> Result<NeighborTable, Error> {
+    // The first column (entries) is the same for every CPU line
+    let stats = std::fs::read_to_string(format!("/proc/net/stat/{}", cache))?;
+    let entries = stats
+        .lines()
+        .nth(1)
+        .and_then(|line| line.split_whitespace().next())
+        .and_then(|entries| u64::from_str_radix(entries, 16).ok())
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid /proc/net/stat/{}", cache),
+            )
+        })?;
+
+    let thresh = |name: &str| -> Result<u64, Error> {
+        read_and_trim(format!("/proc/sys/net/{}/neigh/default/{}", family, name))?
+            .parse()
+            .map_err(|_| Error::new(ErrorKind::InvalidData, format!("Invalid {}", name)))
+    };
+
+    Ok(NeighborTable {
+        entries,
+        gc_thresh1: thresh("gc_thresh1")?,
+        gc_thresh2: thresh("gc_thresh2")?,
+        gc_thresh3: thresh("gc_thresh3")?,
+    })
+}
+
+/// Return the [NeighborTables] usage against their gc_thresh limits.
+///
+/// [NeighborTables]: ../network/struct.NeighborTables.html
+pub fn get_neighbor_tables() -> Result<NeighborTables, Error> {
+    Ok(NeighborTables {
+        ipv4: get_neighbor_table("arp_cache", "ipv4")?,
+        ipv6: get_neighbor_table("ndisc_cache", "ipv6").ok(),
+    })
+}
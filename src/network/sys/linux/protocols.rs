@@ -0,0 +1,238 @@
+use crate::network::{
+    IcmpCounters, IpCounters, IpExtCounters, ProtocolStats, TcpCounters, TcpExtCounters,
+    UdpCounters,
+};
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Error, ErrorKind, Read},
+};
+
+/// The counters of every section, by name, as written by the kernel
+type Sections<'a> = HashMap<&'a str, HashMap<&'a str, &'a str>>;
+
+/// Check the counter is a number, counters are unsigned except a few (eg: MaxConn).
+fn check_counter(name: &str, value: &str, path: &str) -> Result<(), Error> {
+    if value.parse::<i64>().is_err() && value.parse::<u64>().is_err() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid {} counter in {}", name, path),
+        ));
+    }
+    Ok(())
+}
+
+/// Pair the header and value lines (`Section: Name1 Name2` / `Section: 1 2`)
+/// of `/proc/net/snmp` and `/proc/net/netstat`.
+fn parse_paired<'a>(
+    content: &'a str,
+    path: &str,
+    sections: &mut Sections<'a>,
+) -> Result<(), Error> {
+    let mut lines = content.lines();
+
+    while let Some(header) = lines.next() {
+        let values = lines.next().unwrap_or_default();
+        let (section, names) = header.split_once(':').unwrap_or_default();
+        let (value_section, values) = values.split_once(':').unwrap_or_default();
+        if section.is_empty() || section != value_section {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Unpaired header/value lines in {}", path),
+            ));
+        }
+
+        let counters = sections.entry(section).or_default();
+        for (name, value) in names.split_whitespace().zip(values.split_whitespace()) {
+            check_counter(name, value, path)?;
+            counters.insert(name, value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse `/proc/net/snmp6`, one `Name value` pair per line, the section being
+/// the prefix of the name up to the `6` (`Ip6InReceives`, `UdpLite6InErrors`).
+fn parse_snmp6<'a>(content: &'a str, sections: &mut Sections<'a>) -> Result<(), Error> {
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        if let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+            check_counter(name, value, "/proc/net/snmp6")?;
+            let (section, name) = match name.find('6') {
+                Some(pos) => name.split_at(pos + 1),
+                // Not expected, keep it under its full name
+                None => (name, name),
+            };
+            sections.entry(section).or_default().insert(name, value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the content of a file, an empty string if it does not exist.
+fn read_optional(path: &str) -> Result<String, Error> {
+    let mut content = String::new();
+    match File::open(path) {
+        Ok(mut file) => {
+            file.read_to_string(&mut content)?;
+        }
+        // snmp6 is missing when IPv6 is disabled
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    Ok(content)
+}
+
+fn ip_counters(get: impl Fn(&str) -> u64) -> IpCounters {
+    IpCounters {
+        in_receives: get("InReceives"),
+        in_hdr_errors: get("InHdrErrors"),
+        in_addr_errors: get("InAddrErrors"),
+        in_unknown_protos: get("InUnknownProtos"),
+        in_discards: get("InDiscards"),
+        in_delivers: get("InDelivers"),
+        // Named OutForwDatagrams for IPv6
+        forw_datagrams: get("ForwDatagrams").max(get("OutForwDatagrams")),
+        out_requests: get("OutRequests"),
+        out_discards: get("OutDiscards"),
+        out_no_routes: get("OutNoRoutes"),
+        reasm_timeout: get("ReasmTimeout"),
+        reasm_reqds: get("ReasmReqds"),
+        reasm_oks: get("ReasmOKs"),
+        reasm_fails: get("ReasmFails"),
+        frag_oks: get("FragOKs"),
+        frag_fails: get("FragFails"),
+        frag_creates: get("FragCreates"),
+    }
+}
+
+fn icmp_counters(get: impl Fn(&str) -> u64) -> IcmpCounters {
+    IcmpCounters {
+        in_msgs: get("InMsgs"),
+        in_errors: get("InErrors"),
+        in_csum_errors: get("InCsumErrors"),
+        in_dest_unreachs: get("InDestUnreachs"),
+        in_time_excds: get("InTimeExcds"),
+        out_msgs: get("OutMsgs"),
+        out_errors: get("OutErrors"),
+        out_dest_unreachs: get("OutDestUnreachs"),
+        out_time_excds: get("OutTimeExcds"),
+    }
+}
+
+fn tcp_counters(get: impl Fn(&str) -> u64) -> TcpCounters {
+    TcpCounters {
+        active_opens: get("ActiveOpens"),
+        passive_opens: get("PassiveOpens"),
+        attempt_fails: get("AttemptFails"),
+        estab_resets: get("EstabResets"),
+        curr_estab: get("CurrEstab"),
+        in_segs: get("InSegs"),
+        out_segs: get("OutSegs"),
+        retrans_segs: get("RetransSegs"),
+        in_errs: get("InErrs"),
+        out_rsts: get("OutRsts"),
+        in_csum_errors: get("InCsumErrors"),
+    }
+}
+
+fn udp_counters(get: impl Fn(&str) -> u64) -> UdpCounters {
+    UdpCounters {
+        in_datagrams: get("InDatagrams"),
+        no_ports: get("NoPorts"),
+        in_errors: get("InErrors"),
+        out_datagrams: get("OutDatagrams"),
+        rcvbuf_errors: get("RcvbufErrors"),
+        sndbuf_errors: get("SndbufErrors"),
+        in_csum_errors: get("InCsumErrors"),
+        ignored_multi: get("IgnoredMulti"),
+        mem_errors: get("MemErrors"),
+    }
+}
+
+fn tcp_ext_counters(get: impl Fn(&str) -> u64) -> TcpExtCounters {
+    TcpExtCounters {
+        syncookies_sent: get("SyncookiesSent"),
+        syncookies_recv: get("SyncookiesRecv"),
+        syncookies_failed: get("SyncookiesFailed"),
+        prune_called: get("PruneCalled"),
+        listen_overflows: get("ListenOverflows"),
+        listen_drops: get("ListenDrops"),
+        tcp_timeouts: get("TCPTimeouts"),
+        tcp_fast_retrans: get("TCPFastRetrans"),
+        tcp_slow_start_retrans: get("TCPSlowStartRetrans"),
+        tcp_lost_retransmit: get("TCPLostRetransmit"),
+        tcp_syn_retrans: get("TCPSynRetrans"),
+        tcp_backlog_drop: get("TCPBacklogDrop"),
+        tcp_abort_on_timeout: get("TCPAbortOnTimeout"),
+        tcp_abort_on_memory: get("TCPAbortOnMemory"),
+    }
+}
+
+fn ip_ext_counters(get: impl Fn(&str) -> u64) -> IpExtCounters {
+    IpExtCounters {
+        in_no_routes: get("InNoRoutes"),
+        in_truncated_pkts: get("InTruncatedPkts"),
+        in_mcast_pkts: get("InMcastPkts"),
+        out_mcast_pkts: get("OutMcastPkts"),
+        in_bcast_pkts: get("InBcastPkts"),
+        out_bcast_pkts: get("OutBcastPkts"),
+        in_octets: get("InOctets"),
+        out_octets: get("OutOctets"),
+        in_csum_errors: get("InCsumErrors"),
+    }
+}
+
+/// Return the [ProtocolStats] of the IP, ICMP, TCP and UDP protocols.
+///
+/// [ProtocolStats]: ../network/struct.ProtocolStats.html
+pub fn get_protocol_stats() -> Result<ProtocolStats, Error> {
+    let snmp = read_optional("/proc/net/snmp")?;
+    let netstat = read_optional("/proc/net/netstat")?;
+    let snmp6 = read_optional("/proc/net/snmp6")?;
+
+    let mut sections = Sections::new();
+    parse_paired(&snmp, "/proc/net/snmp", &mut sections)?;
+    parse_paired(&netstat, "/proc/net/netstat", &mut sections)?;
+    parse_snmp6(&snmp6, &mut sections)?;
+
+    let section = |section: &'static str| {
+        let counters = sections.get(section);
+        move |name: &str| {
+            counters
+                .and_then(|counters| counters.get(name))
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(0)
+        }
+    };
+
+    Ok(ProtocolStats {
+        ip: ip_counters(section("Ip")),
+        icmp: icmp_counters(section("Icmp")),
+        tcp: tcp_counters(section("Tcp")),
+        udp: udp_counters(section("Udp")),
+        udp_lite: udp_counters(section("UdpLite")),
+        ip6: ip_counters(section("Ip6")),
+        icmp6: icmp_counters(section("Icmp6")),
+        udp6: udp_counters(section("Udp6")),
+        udp_lite6: udp_counters(section("UdpLite6")),
+        tcp_ext: tcp_ext_counters(section("TcpExt")),
+        ip_ext: ip_ext_counters(section("IpExt")),
+        sections: sections
+            .iter()
+            .map(|(section, counters)| {
+                let counters = counters
+                    .iter()
+                    // Unsigned counters above i64::MAX are saturated
+                    .map(|(name, value)| {
+                        (name.to_string(), value.parse::<i64>().unwrap_or(i64::MAX))
+                    })
+                    .collect();
+                (section.to_string(), counters)
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,135 @@
+use crate::network::{DefaultGateway, Route};
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Error, ErrorKind},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+// RTF_LOCAL from include/uapi/linux/ipv6_route.h
+const RTF_LOCAL: u32 = 0x8000_0000;
+
+#[inline]
+fn invalid(path: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("Invalid route in {}", path))
+}
+
+/// Parse an IPv4 address stored as an u32 in host order.
+#[inline]
+fn parse_ipv4(value: &str) -> Option<Ipv4Addr> {
+    let value = u32::from_str_radix(value, 16).ok()?;
+    Some(Ipv4Addr::from(value.to_ne_bytes()))
+}
+
+/// Parse a line of `/proc/net/route` (without the header).
+fn parse_route(line: &str) -> Option<Route> {
+    // Iface Destination Gateway Flags RefCnt Use Metric Mask MTU Window IRTT
+    let mut fields = line.split_whitespace();
+    let interface = fields.next()?.to_owned();
+    let destination = parse_ipv4(fields.next()?)?;
+    let gateway = parse_ipv4(fields.next()?)?;
+    let flags = u32::from_str_radix(fields.next()?, 16).ok()?;
+    let metric = fields.nth(2)?.parse().ok()?;
+    let mask = parse_ipv4(fields.next()?)?;
+    let mtu = fields.next()?.parse().ok()?;
+
+    Some(Route {
+        destination: IpAddr::V4(destination),
+        prefix_len: u32::from(mask).count_ones() as u8,
+        gateway: Some(IpAddr::V4(gateway)).filter(|_| !gateway.is_unspecified()),
+        interface,
+        metric,
+        flags,
+        mtu: Some(mtu).filter(|mtu| *mtu != 0),
+    })
+}
+
+/// Parse a line of `/proc/net/ipv6_route`.
+fn parse_route6(line: &str) -> Option<Route> {
+    // dest dest_prefix_len src src_prefix_len nexthop metric refcnt use flags iface
+    let mut fields = line.split_whitespace();
+    let destination = Ipv6Addr::from(u128::from_str_radix(fields.next()?, 16).ok()?);
+    let prefix_len = u8::from_str_radix(fields.next()?, 16).ok()?;
+    let gateway = Ipv6Addr::from(u128::from_str_radix(fields.nth(2)?, 16).ok()?);
+    let metric = u32::from_str_radix(fields.next()?, 16).ok()?;
+    let flags = u32::from_str_radix(fields.nth(2)?, 16).ok()?;
+    let interface = fields.next()?.to_owned();
+
+    Some(Route {
+        destination: IpAddr::V6(destination),
+        prefix_len,
+        gateway: Some(IpAddr::V6(gateway)).filter(|_| !gateway.is_unspecified()),
+        interface,
+        metric,
+        flags,
+        mtu: None,
+    })
+}
+
+fn read_routes(
+    path: &str,
+    skip_header: bool,
+    parse: fn(&str) -> Option<Route>,
+    routes: &mut Vec<Route>,
+) -> Result<(), Error> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        // ipv6_route is missing when IPv6 is disabled
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    let mut file = BufReader::with_capacity(4096, file);
+
+    let mut header = skip_header;
+    let mut line = String::with_capacity(256);
+    while file.read_line(&mut line)? != 0 {
+        if header || line.trim().is_empty() {
+            header = false;
+            line.clear();
+            continue;
+        }
+        match parse(&line) {
+            Some(route) => routes.push(route),
+            None => return Err(invalid(path)),
+        }
+        line.clear();
+    }
+
+    Ok(())
+}
+
+/// Return a Vec of the IPv4 and IPv6 [Route].
+///
+/// IPv4 routes are the ones of the main table. `/proc/net/ipv6_route` dumps every
+/// table, the routes to the local addresses (RTF_LOCAL) are left out.
+///
+/// [Route]: ../network/struct.Route.html
+pub fn get_routes() -> Result<Vec<Route>, Error> {
+    let mut routes = Vec::new();
+    read_routes("/proc/net/route", true, parse_route, &mut routes)?;
+    read_routes("/proc/net/ipv6_route", false, parse_route6, &mut routes)?;
+    routes.retain(|route| route.flags & RTF_LOCAL == 0);
+    Ok(routes)
+}
+
+/// Return the [DefaultGateway] (lowest metric default route which is up) of IPv4 and IPv6.
+///
+/// [DefaultGateway]: ../network/struct.DefaultGateway.html
+pub fn get_default_gateway() -> Result<DefaultGateway, Error> {
+    let mut gateway = DefaultGateway::default();
+
+    for route in get_routes()? {
+        if !route.is_default() || !route.is_up() {
+            continue;
+        }
+        let best = match route.destination {
+            IpAddr::V4(_) => &mut gateway.ipv4,
+            IpAddr::V6(_) => &mut gateway.ipv6,
+        };
+        if best.as_ref().is_none_or(|best| route.metric < best.metric) {
+            *best = Some(route);
+        }
+    }
+
+    Ok(gateway)
+}
@@ -0,0 +1,120 @@
+use crate::network::{InetSocket, TcpState};
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Error, ErrorKind},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+#[inline]
+fn invalid(path: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("Invalid socket entry in {}", path),
+    )
+}
+
+/// Parse an `ADDRESS:PORT` pair, the address being stored as u32 words in host order.
+fn parse_endpoint(value: &str) -> Option<(IpAddr, u16)> {
+    let (address, port) = value.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut bytes = [0u8; 16];
+    match address.len() {
+        8 | 32 => {
+            for (i, chunk) in address.as_bytes().chunks(8).enumerate() {
+                let word = u32::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+                bytes[i * 4..i * 4 + 4].copy_from_slice(&word.to_ne_bytes());
+            }
+        }
+        _ => return None,
+    }
+
+    let address = if address.len() == 8 {
+        IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]))
+    } else {
+        IpAddr::V6(Ipv6Addr::from(bytes))
+    };
+    Some((address, port))
+}
+
+/// Parse a line of `/proc/net/{tcp,udp}{,6}` (without the header).
+fn parse_socket(line: &str, udp: bool) -> Option<InetSocket> {
+    let mut fields = line.split_whitespace().skip(1);
+    let (local_address, local_port) = parse_endpoint(fields.next()?)?;
+    let (remote_address, remote_port) = parse_endpoint(fields.next()?)?;
+    let state = TcpState::from_code(u8::from_str_radix(fields.next()?, 16).ok()?);
+    let (tx_queue, rx_queue) = fields.next()?.split_once(':')?;
+    let (timer_active, timer_expires) = fields.next()?.split_once(':')?;
+    let retransmits = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let uid = fields.next()?.parse().ok()?;
+    // Skip the timeout
+    let inode = fields.nth(1)?.parse().ok()?;
+    let drops = if udp {
+        // Skip the ref and pointer
+        Some(fields.nth(2)?.parse().ok()?)
+    } else {
+        None
+    };
+
+    Some(InetSocket {
+        local_address,
+        local_port,
+        remote_address,
+        remote_port,
+        state,
+        tx_queue: u64::from_str_radix(tx_queue, 16).ok()?,
+        rx_queue: u64::from_str_radix(rx_queue, 16).ok()?,
+        timer_active: u8::from_str_radix(timer_active, 16).ok()?,
+        timer_expires: u64::from_str_radix(timer_expires, 16).ok()?,
+        retransmits,
+        uid,
+        inode,
+        drops,
+    })
+}
+
+fn read_sockets(paths: &[&str], udp: bool) -> Result<Vec<InetSocket>, Error> {
+    let mut sockets = Vec::new();
+
+    for path in paths {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            // tcp6/udp6 are missing when IPv6 is disabled
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        let mut file = BufReader::with_capacity(8192, file);
+
+        let mut header = true;
+        let mut line = String::with_capacity(256);
+        while file.read_line(&mut line)? != 0 {
+            if header {
+                header = false;
+                line.clear();
+                continue;
+            }
+            match parse_socket(&line, udp) {
+                Some(socket) => sockets.push(socket),
+                None => return Err(invalid(path)),
+            }
+            line.clear();
+        }
+    }
+
+    Ok(sockets)
+}
+
+/// Return a Vec of [InetSocket] for the IPv4 and IPv6 TCP sockets.
+///
+/// [InetSocket]: ../network/struct.InetSocket.html
+pub fn get_tcp_sockets() -> Result<Vec<InetSocket>, Error> {
+    read_sockets(&["/proc/net/tcp", "/proc/net/tcp6"], false)
+}
+
+/// Return a Vec of [InetSocket] for the IPv4 and IPv6 UDP sockets.
+///
+/// [InetSocket]: ../network/struct.InetSocket.html
+pub fn get_udp_sockets() -> Result<Vec<InetSocket>, Error> {
+    read_sockets(&["/proc/net/udp", "/proc/net/udp6"], true)
+}
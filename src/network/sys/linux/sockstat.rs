@@ -0,0 +1,133 @@
+use crate::network::{ConntrackUsage, ProtocolMemLimits, SocketSummary, SockstatEntry};
+use crate::read_and_trim;
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Error, ErrorKind},
+};
+
+/// Parse a `/proc/net/sockstat` file into the summary.
+fn read_sockstat(path: &str, summary: &mut SocketSummary) -> Result<(), Error> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        // sockstat6 is missing when IPv6 is disabled
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    let mut file = BufReader::with_capacity(1024, file);
+
+    let mut line = String::with_capacity(64);
+    while file.read_line(&mut line)? != 0 {
+        // `PROTO: key value key value ...`
+        let (protocol, values) = match line.split_once(':') {
+            Some(parts) => parts,
+            None => {
+                line.clear();
+                continue;
+            }
+        };
+        let mut values = values.split_whitespace();
+        let mut pairs = Vec::new();
+        while let (Some(key), Some(value)) = (values.next(), values.next()) {
+            let value = value.parse::<u64>().map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid {} {} in {}", protocol, key, path),
+                )
+            })?;
+            pairs.push((key, value));
+        }
+
+        let entry = match protocol {
+            "sockets" => {
+                for (key, value) in pairs {
+                    if key == "used" {
+                        summary.sockets_used = value;
+                    }
+                }
+                line.clear();
+                continue;
+            }
+            "TCP" => &mut summary.tcp,
+            "UDP" => &mut summary.udp,
+            "UDPLITE" => &mut summary.udplite,
+            "RAW" => &mut summary.raw,
+            "FRAG" => &mut summary.frag,
+            "TCP6" => &mut summary.tcp6,
+            "UDP6" => &mut summary.udp6,
+            "UDPLITE6" => &mut summary.udplite6,
+            "RAW6" => &mut summary.raw6,
+            "FRAG6" => &mut summary.frag6,
+            _ => {
+                line.clear();
+                continue;
+            }
+        };
+        fill_entry(entry, &pairs);
+        line.clear();
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn fill_entry(entry: &mut SockstatEntry, pairs: &[(&str, u64)]) {
+    for (key, value) in pairs {
+        match *key {
+            "inuse" => entry.inuse = *value,
+            "orphan" => entry.orphan = *value,
+            "tw" => entry.tw = *value,
+            "alloc" => entry.alloc = *value,
+            "mem" => entry.mem = *value,
+            "memory" => entry.memory = *value,
+            _ => {}
+        }
+    }
+}
+
+/// Parse the `min pressure max` triplet of `tcp_mem`/`udp_mem`.
+fn read_mem_limits(path: &str) -> Result<ProtocolMemLimits, Error> {
+    let content = read_and_trim(path)?;
+    let values: Vec<u64> = content
+        .split_whitespace()
+        .filter_map(|val| val.parse().ok())
+        .collect();
+
+    match values[..] {
+        [min, pressure, max] => Ok(ProtocolMemLimits { min, pressure, max }),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid {}", path),
+        )),
+    }
+}
+
+/// Read the conntrack table usage, None if nf_conntrack is not loaded.
+fn read_conntrack() -> Option<ConntrackUsage> {
+    let read = |name: &str| -> Option<u64> {
+        read_and_trim(format!("/proc/sys/net/netfilter/{}", name))
+            .ok()?
+            .parse()
+            .ok()
+    };
+
+    Some(ConntrackUsage {
+        count: read("nf_conntrack_count")?,
+        max: read("nf_conntrack_max")?,
+    })
+}
+
+/// Return the [SocketSummary] of the sockets usage, memory limits and conntrack table.
+///
+/// [SocketSummary]: ../network/struct.SocketSummary.html
+pub fn get_socket_summary() -> Result<SocketSummary, Error> {
+    let mut summary = SocketSummary::default();
+
+    read_sockstat("/proc/net/sockstat", &mut summary)?;
+    read_sockstat("/proc/net/sockstat6", &mut summary)?;
+    summary.tcp_mem = read_mem_limits("/proc/sys/net/ipv4/tcp_mem")?;
+    summary.udp_mem = read_mem_limits("/proc/sys/net/ipv4/udp_mem")?;
+    summary.conntrack = read_conntrack();
+
+    Ok(summary)
+}
@@ -0,0 +1,56 @@
+use crate::network::SoftnetStat;
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Error, ErrorKind},
+};
+
+/// Return a Vec of [SoftnetStat], one per online CPU.
+///
+/// Before Linux 5.10, the CPU number is not reported and is deduced from
+/// the line number, which is wrong if some CPUs are offline.
+///
+/// [SoftnetStat]: ../network/struct.SoftnetStat.html
+pub fn get_softnet_stats() -> Result<Vec<SoftnetStat>, Error> {
+    let file = File::open("/proc/net/softnet_stat")?;
+    let mut file = BufReader::with_capacity(2048, file);
+    let mut stats = Vec::new();
+
+    let mut line = String::with_capacity(160);
+    while file.read_line(&mut line)? != 0 {
+        let mut values = Vec::with_capacity(13);
+        for value in line.split_whitespace() {
+            match u64::from_str_radix(value, 16) {
+                Ok(val) => values.push(val),
+                Err(_) => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "Invalid counter in /proc/net/softnet_stat",
+                    ));
+                }
+            }
+        }
+        // Old kernels have 10 columns, flow_limit_count (3.11) and backlog_len/cpu (5.10) came later
+        if values.len() < 10 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Missing counters in /proc/net/softnet_stat",
+            ));
+        }
+        let get = |idx: usize| values.get(idx).copied().unwrap_or(0);
+
+        stats.push(SoftnetStat {
+            cpu: values.get(12).map_or(stats.len(), |cpu| *cpu as usize),
+            processed: get(0),
+            dropped: get(1),
+            time_squeeze: get(2),
+            cpu_collision: get(8),
+            received_rps: get(9),
+            flow_limit_count: get(10),
+            backlog_len: get(11),
+        });
+        line.clear();
+    }
+
+    Ok(stats)
+}
@@ -0,0 +1,81 @@
+use crate::network::WirelessStats;
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Error, ErrorKind},
+};
+
+/// Parse a line of `/proc/net/wireless` (without the headers).
+fn parse_wireless(line: &str) -> Option<WirelessStats> {
+    // Values updated since the last read are suffixed by a dot
+    let level = |value: &str| value.trim_end_matches('.').parse::<f64>().ok();
+
+    let (interface, values) = line.split_once(':')?;
+    let mut values = values.split_whitespace();
+    let status = u16::from_str_radix(values.next()?, 16).ok()?;
+    let link_quality = level(values.next()?)?;
+    let signal_level = level(values.next()?)?;
+    let noise_level = level(values.next()?)?;
+    let mut counters = [0u64; 6];
+    for counter in counters.iter_mut() {
+        *counter = values.next()?.parse().ok()?;
+    }
+
+    Some(WirelessStats {
+        interface: interface.trim().to_owned(),
+        status,
+        link_quality,
+        signal_level,
+        // Without a noise measurement, the kernel reports a raw 0, which shows up as
+        // -256 when the driver uses dBm (the 0x100 offset) and as 0 otherwise.
+        // A noise floor of 0 dBm is not a real measurement either way.
+        noise_level: Some(noise_level).filter(|noise| *noise != -256.0 && *noise != 0.0),
+        discarded_nwid: counters[0],
+        discarded_crypt: counters[1],
+        discarded_frag: counters[2],
+        discarded_retry: counters[3],
+        discarded_misc: counters[4],
+        missed_beacon: counters[5],
+    })
+}
+
+/// Return a Vec of [WirelessStats] for each wireless interface.
+///
+/// If there's no wireless extension support (no `/proc/net/wireless`), an empty Vec is returned.
+///
+/// [WirelessStats]: ../network/struct.WirelessStats.html
+pub fn get_wireless_stats() -> Result<Vec<WirelessStats>, Error> {
+    let file = match File::open("/proc/net/wireless") {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    parse_wireless_stats(BufReader::with_capacity(1024, file))
+}
+
+/// Parse the content of `/proc/net/wireless`, including its 2 header lines.
+pub fn parse_wireless_stats<R: BufRead>(mut reader: R) -> Result<Vec<WirelessStats>, Error> {
+    let mut stats = Vec::new();
+
+    let mut line_skip = 0;
+    let mut line = String::with_capacity(128);
+    while reader.read_line(&mut line)? != 0 {
+        line_skip += 1;
+        if line_skip < 3 {
+            line.clear();
+            continue;
+        }
+        match parse_wireless(&line) {
+            Some(stat) => stats.push(stat),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Invalid entry in /proc/net/wireless",
+                ));
+            }
+        }
+        line.clear();
+    }
+
+    Ok(stats)
+}
@@ -138,6 +138,9 @@ pub fn get_ionets() -> Result<Vec<IoNet>, Error> {
                 tx_packets: msg.ifm_data.ifi_opackets as u64,
                 tx_errs: msg.ifm_data.ifi_oerrors as u64,
                 tx_drop: msg.ifm_snd_drops as u64, // Not sure about this one, can't find enough doc
+                rx_multicast: msg.ifm_data.ifi_imcasts as u64,
+                tx_colls: msg.ifm_data.ifi_collisions as u64,
+                ..Default::default()
             })
         })
         .collect()
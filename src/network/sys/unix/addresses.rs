@@ -0,0 +1,185 @@
+use crate::network::{InterfaceAddresses, Ipv4Address, Ipv6Address, Ipv6Scope};
+use crate::to_str;
+
+use libc::{ifaddrs, sockaddr, sockaddr_in, sockaddr_in6};
+use std::io::Error;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Convert a sockaddr to an IPv4 address, if it's one.
+#[inline]
+fn to_ipv4(addr: *const sockaddr) -> Option<Ipv4Addr> {
+    if addr.is_null() || unsafe { (*addr).sa_family } as i32 != libc::AF_INET {
+        return None;
+    }
+    let addr = unsafe { &*(addr as *const sockaddr_in) };
+    Some(Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)))
+}
+
+/// Convert a sockaddr to an IPv6 address, if it's one.
+#[inline]
+fn to_ipv6(addr: *const sockaddr) -> Option<Ipv6Addr> {
+    if addr.is_null() || unsafe { (*addr).sa_family } as i32 != libc::AF_INET6 {
+        return None;
+    }
+    let addr = unsafe { &*(addr as *const sockaddr_in6) };
+    #[allow(unused_mut)]
+    let mut bytes = addr.sin6_addr.s6_addr;
+    // The KAME stack embeds the scope id in the bytes 2-3 of the
+    // link-local addresses (fe80:4::1 for fe80::1%4), clear it.
+    #[cfg(target_os = "macos")]
+    if bytes[0] == 0xfe && bytes[1] & 0xc0 == 0x80 {
+        bytes[2] = 0;
+        bytes[3] = 0;
+    }
+    Some(Ipv6Addr::from(bytes))
+}
+
+/// Broadcast or destination address, depending on IFF_POINTOPOINT.
+#[inline]
+fn ifu_addr(ifa: &ifaddrs) -> *const sockaddr {
+    #[cfg(target_os = "linux")]
+    return ifa.ifa_ifu;
+    #[cfg(target_os = "macos")]
+    return ifa.ifa_dstaddr;
+}
+
+/// Guess the scope of an IPv6 address from its prefix.
+#[cfg(not(target_os = "linux"))]
+fn ipv6_scope(address: &Ipv6Addr) -> Ipv6Scope {
+    let first = address.segments()[0];
+    if address.is_loopback() {
+        Ipv6Scope::Host
+    } else if first & 0xffc0 == 0xfe80 {
+        Ipv6Scope::Link
+    } else if first & 0xffc0 == 0xfec0 {
+        Ipv6Scope::Site
+    } else {
+        Ipv6Scope::Global
+    }
+}
+
+/// Get the scope and the (temporary, deprecated) flags of the IPv6 addresses from `/proc/net/if_inet6`.
+#[cfg(target_os = "linux")]
+fn get_inet6_entries() -> std::collections::HashMap<(String, Ipv6Addr), (Ipv6Scope, bool, bool)> {
+    // IPV6_ADDR_* scopes from include/net/ipv6.h
+    const IPV6_ADDR_LOOPBACK: u32 = 0x10;
+    const IPV6_ADDR_LINKLOCAL: u32 = 0x20;
+    const IPV6_ADDR_SITELOCAL: u32 = 0x40;
+    // IFA_F_* from include/uapi/linux/if_addr.h
+    const IFA_F_TEMPORARY: u32 = 0x01;
+    const IFA_F_DEPRECATED: u32 = 0x20;
+
+    let content = std::fs::read_to_string("/proc/net/if_inet6").unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| {
+            // address ifindex prefix_len scope flags name
+            let mut fields = line.split_whitespace();
+            let address = u128::from_str_radix(fields.next()?, 16).ok()?;
+            let scope = match u32::from_str_radix(fields.nth(2)?, 16).ok()? {
+                IPV6_ADDR_LOOPBACK => Ipv6Scope::Host,
+                IPV6_ADDR_LINKLOCAL => Ipv6Scope::Link,
+                IPV6_ADDR_SITELOCAL => Ipv6Scope::Site,
+                _ => Ipv6Scope::Global,
+            };
+            let flags = u32::from_str_radix(fields.next()?, 16).ok()?;
+            let name = fields.next()?.to_owned();
+            Some((
+                (name, Ipv6Addr::from(address)),
+                (
+                    scope,
+                    flags & IFA_F_TEMPORARY != 0,
+                    flags & IFA_F_DEPRECATED != 0,
+                ),
+            ))
+        })
+        .collect()
+}
+
+/// Return a Vec of [InterfaceAddresses] with the IPv4/IPv6 addresses of every interface.
+///
+/// Interfaces without any address are listed with empty `ipv4` and `ipv6`.
+///
+/// [InterfaceAddresses]: ../network/struct.InterfaceAddresses.html
+pub fn get_interface_addresses() -> Result<Vec<InterfaceAddresses>, Error> {
+    let mut ifap: *mut ifaddrs = std::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut ifap) } == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    #[cfg(target_os = "linux")]
+    let inet6_entries = get_inet6_entries();
+
+    let mut interfaces: Vec<InterfaceAddresses> = Vec::new();
+    let mut current = ifap;
+    while !current.is_null() {
+        let ifa = unsafe { &*current };
+        current = ifa.ifa_next;
+
+        let name = to_str(ifa.ifa_name);
+        let flags = ifa.ifa_flags as i32;
+        // Keep the order in which the interfaces are returned
+        let idx = match interfaces.iter().position(|i| i.interface == name) {
+            Some(idx) => idx,
+            None => {
+                interfaces.push(InterfaceAddresses {
+                    interface: name.to_owned(),
+                    is_up: flags & libc::IFF_UP != 0,
+                    is_running: flags & libc::IFF_RUNNING != 0,
+                    is_loopback: flags & libc::IFF_LOOPBACK != 0,
+                    is_multicast: flags & libc::IFF_MULTICAST != 0,
+                    is_point_to_point: flags & libc::IFF_POINTOPOINT != 0,
+                    ..Default::default()
+                });
+                interfaces.len() - 1
+            }
+        };
+        let interface = &mut interfaces[idx];
+        let p2p = flags & libc::IFF_POINTOPOINT != 0;
+
+        if let Some(address) = to_ipv4(ifa.ifa_addr) {
+            let netmask = to_ipv4(ifa.ifa_netmask).unwrap_or(Ipv4Addr::UNSPECIFIED);
+            let other = to_ipv4(ifu_addr(ifa));
+            interface.ipv4.push(Ipv4Address {
+                address,
+                netmask,
+                prefix_len: u32::from(netmask).count_ones() as u8,
+                broadcast: other.filter(|_| !p2p && flags & libc::IFF_BROADCAST != 0),
+                peer: other.filter(|_| p2p),
+            });
+        } else if let Some(address) = to_ipv6(ifa.ifa_addr) {
+            let prefix_len = to_ipv6(ifa.ifa_netmask)
+                .map_or(0, |netmask| u128::from(netmask).count_ones() as u8);
+            // The kernel tells the scope, only guess it on macOS
+            #[cfg(target_os = "linux")]
+            let (scope, temporary, deprecated) = inet6_entries
+                .get(&(name.to_owned(), address))
+                .copied()
+                .unwrap_or((Ipv6Scope::Global, false, false));
+            #[cfg(not(target_os = "linux"))]
+            let (scope, temporary, deprecated) = (ipv6_scope(&address), false, false);
+
+            interface.ipv6.push(Ipv6Address {
+                address,
+                prefix_len,
+                peer: to_ipv6(ifu_addr(ifa)).filter(|_| p2p),
+                scope,
+                temporary,
+                deprecated,
+            });
+        }
+    }
+
+    unsafe { libc::freeifaddrs(ifap) };
+
+    Ok(interfaces)
+}
+
+/// Return the index of an interface, None if it does not exist.
+pub(crate) fn interface_index(name: &str) -> Option<u32> {
+    let name = std::ffi::CString::new(name).ok()?;
+    match unsafe { libc::if_nametoindex(name.as_ptr()) } {
+        0 => None,
+        index => Some(index),
+    }
+}
@@ -0,0 +1,3 @@
+mod addresses;
+
+pub use addresses::*;
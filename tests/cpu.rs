@@ -1,5 +1,5 @@
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
 mod cpu {
     use sys_metrics::cpu::*;
 
@@ -1,20 +1,550 @@
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
 mod disks {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use std::time::Duration;
     use sys_metrics::disks::*;
 
     #[test]
     fn test_partitions_physical() {
         let partitions = get_partitions_physical().unwrap();
 
-        assert!(partitions.len() > 0);
+        assert!(!partitions.is_empty());
     }
 
     #[test]
     fn test_physical_ioblocks() {
         let stats = get_physical_ioblocks().unwrap();
 
-        assert!(stats.len() > 0);
+        assert!(!stats.is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_block_devices() {
+        let devices = get_block_devices().unwrap();
+
+        assert!(!devices.is_empty());
+        for device in devices {
+            assert!(!device.name.is_empty());
+            assert!(device.logical_sector_size >= 512);
+            assert!(device.physical_sector_size >= device.logical_sector_size);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_mounts() {
+        let mounts = get_mounts().unwrap();
+
+        assert!(mounts.iter().any(|mount| mount.mount_point == "/"));
+        for mount in mounts {
+            assert!(mount.mount_point.starts_with('/'));
+            assert!(!mount.fstype.is_empty());
+            assert!(!mount.mount_point.contains("\\040"));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_mountinfo() {
+        let content = "\
+36 35 98:0 /mnt1 /mnt/with\\040space rw,noatime shared:1 master:2 - ext3 /dev/root rw,errors=continue
+37 36 0:42 /back\\134slash\\011tab /srv/new\\012line ro - nfs4 server:/export\\040dir rw,vers=4.2
+";
+        let mounts = parse_mountinfo(content.as_bytes()).unwrap();
+        assert_eq!(mounts.len(), 2);
+
+        let ext3 = &mounts[0];
+        assert_eq!((ext3.mount_id, ext3.parent_id), (36, 35));
+        assert_eq!((ext3.major, ext3.minor), (98, 0));
+        assert_eq!(ext3.root, "/mnt1");
+        assert_eq!(ext3.mount_point, "/mnt/with space");
+        assert_eq!(ext3.mount_options, ["rw", "noatime"]);
+        assert_eq!(ext3.optional_fields, ["shared:1", "master:2"]);
+        assert_eq!(ext3.fstype, "ext3");
+        assert_eq!(ext3.source, "/dev/root");
+        assert_eq!(ext3.super_options, ["rw", "errors=continue"]);
+
+        let nfs = &mounts[1];
+        assert_eq!(nfs.root, "/back\\slash\ttab");
+        assert_eq!(nfs.mount_point, "/srv/new\nline");
+        assert!(nfs.optional_fields.is_empty());
+        assert_eq!(nfs.fstype, "nfs4");
+        assert_eq!(nfs.source, "server:/export dir");
+        assert!(nfs.is_read_only());
+
+        // Missing the separator
+        assert!(parse_mountinfo("36 35 98:0 / / rw ext3 /dev/root rw\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_disk_usage() {
+        let usage = disk_usage("/").unwrap();
+
+        assert!(usage.total > 0);
+        assert!(usage.used <= usage.total);
+        assert!(usage.available <= usage.free);
+        assert!(usage.reserved() <= usage.free);
+        assert!(usage.free_inodes <= usage.total_inodes);
+        assert!(usage.block_size > 0);
+        assert!(usage.max_filename_len > 0);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_mount_device() {
+        let root = get_mount_device("/").unwrap();
+        assert_eq!(root.mount_point, "/");
+
+        let proc = get_mount_device("/proc/self").unwrap();
+        assert_eq!(proc.mount_point, "/proc");
+        assert!(proc.disks.is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_md_arrays() {
+        let arrays = get_md_arrays().unwrap();
+
+        for array in arrays {
+            assert!(array.name.starts_with("md"));
+            assert!(array.degraded <= array.raid_disks);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_mdstat() {
+        let content = "\
+Personalities : [raid1] [raid6] [raid5] [raid4]
+md1 : active raid5 sdd1[3] sdc1[2](F) sdb1[1] sda1[0] sde1[4](S)
+      2930272256 blocks super 1.2 level 5, 512k chunk, algorithm 2 [4/3] [UU_U]
+      [==>..................]  recovery = 12.6% (123456/979532) finish=127.5min speed=33440K/sec
+      bitmap: 0/8 pages [0KB], 65536KB chunk
+
+md0 : active (auto-read-only) raid1 sdb2[1] sda2[0]
+      1048512 blocks super 1.2 [2/2] [UU]
+        resync=DELAYED
+
+md2 : inactive sdf1[0](S)
+      976630488 blocks super 1.2
+
+unused devices: <none>
+";
+        let arrays = parse_mdstat(content.as_bytes()).unwrap();
+        assert_eq!(arrays.len(), 3);
+
+        let md1 = &arrays[0];
+        assert_eq!(md1.name, "md1");
+        assert_eq!(md1.state, "active");
+        assert_eq!(md1.level, "raid5");
+        assert_eq!(md1.size, 2930272256 * 1024);
+        assert_eq!((md1.raid_disks, md1.active_disks, md1.degraded), (4, 3, 1));
+        assert_eq!(md1.members.len(), 5);
+        let faulty: Vec<&str> = md1
+            .members
+            .iter()
+            .filter(|m| m.faulty)
+            .map(|m| m.name.as_str())
+            .collect();
+        let spare: Vec<&str> = md1
+            .members
+            .iter()
+            .filter(|m| m.spare)
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(faulty, ["sdc1"]);
+        assert_eq!(spare, ["sde1"]);
+        assert_eq!(md1.members[1].descriptor, 2);
+        let sync = md1.sync.as_ref().unwrap();
+        assert_eq!(sync.action, "recovery");
+        assert!(!sync.pending);
+        assert_eq!(sync.progress, 12.6);
+        assert_eq!((sync.done, sync.total), (123456, 979532));
+        assert_eq!(sync.finish, Some(127.5 * 60.0));
+        assert_eq!(sync.speed, 33440);
+
+        let md0 = &arrays[1];
+        assert!(md0.read_only);
+        assert_eq!(md0.level, "raid1");
+        assert_eq!((md0.raid_disks, md0.degraded), (2, 0));
+        let sync = md0.sync.as_ref().unwrap();
+        assert_eq!(sync.action, "resync");
+        assert!(sync.pending);
+
+        let md2 = &arrays[2];
+        assert_eq!(md2.state, "inactive");
+        assert!(md2.level.is_empty());
+        assert!(md2.members[0].spare);
+        assert!(md2.sync.is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_filesystem_kind() {
+        let mounts = get_mounts().unwrap();
+        let proc = mounts.iter().find(|m| m.fstype == "proc").unwrap();
+        assert_eq!(get_filesystem_kind(proc).unwrap(), FilesystemKind::Pseudo);
+
+        // The override only applies to the query it is set on
+        let overridden = PartitionQuery::new()
+            .filesystem_kind("proc", FilesystemKind::Virtual)
+            .include_kind(FilesystemKind::Virtual)
+            .skip_usage()
+            .run()
+            .unwrap();
+        assert!(overridden.iter().any(|disk| disk.fstype == "proc"));
+        assert_eq!(get_filesystem_kind(proc).unwrap(), FilesystemKind::Pseudo);
+
+        for disk in get_partitions_physical().unwrap() {
+            assert_eq!(disk.kind, FilesystemKind::Physical);
+        }
+    }
+
+    #[test]
+    fn test_disk_usage_with_timeout() {
+        let usage = disk_usage_with_timeout("/", Duration::from_secs(5)).unwrap();
+        assert!(usage.total > 0);
+
+        let err = disk_usage_with_timeout("/does/not/exist", Duration::from_secs(5)).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_partitions_with_timeout() {
+        let partitions = get_partitions_with_timeout(Duration::from_secs(1)).unwrap();
+
+        assert!(!partitions.is_empty());
+        for disk in partitions {
+            if disk.error.is_none() {
+                assert_eq!(disk.total_space, disk.usage.total / (1024 * 1024));
+            }
+        }
+    }
+
+    #[test]
+    fn test_partition_query() {
+        let all = PartitionQuery::new().skip_usage().run().unwrap();
+        assert!(!all.is_empty());
+        assert!(all.iter().all(|disk| disk.usage.total == 0));
+
+        let root = PartitionQuery::new()
+            .include_mount_point("/")
+            .run()
+            .unwrap();
+        assert!(root.iter().all(|disk| disk.mount_point == "/"));
+
+        let dedup = PartitionQuery::new()
+            .dedup_bind_mounts()
+            .skip_usage()
+            .run()
+            .unwrap();
+        assert!(dedup.len() <= all.len());
+
+        let excluded = PartitionQuery::new()
+            .exclude_mount_point("*")
+            .skip_usage()
+            .run()
+            .unwrap();
+        assert!(excluded.is_empty());
+
+        assert!(PartitionQuery::new().include_device("[").run().is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_nfs_mountstats() {
+        for mount in get_nfs_mountstats().unwrap() {
+            assert!(mount.fstype.starts_with("nfs"));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_nfs_mountstats() {
+        let content = "\
+device rootfs mounted on / with fstype rootfs
+device server:/export\\040dir mounted on /mnt/nfs with fstype nfs4 statvers=1.1
+\topts:\trw,vers=4.2,rsize=1048576,wsize=1048576,hard,proto=tcp,timeo=600,retrans=2,sec=sys
+\tage:\t3600
+\tcaps:\tcaps=0x3ffdf,wtmult=512,dtsize=32768,bsize=0,namlen=255
+\tsec:\tflavor=1,pseudoflavor=1
+\tevents:\t52 2170 0 10 41 20 2598 0 0 11 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+\tbytes:\t1000 2000 300 400 5000 6000 7 8
+\tRPC iostats version: 1.1  p/v: 100003/4 (nfs)
+\txprt:\ttcp 832 1 1 0 16 5000 4998 2 80000 0 2 100 200
+\tper-op statistics
+\t        NULL: 1 1 0 44 24 0 0 0 0
+\t        READ: 100 101 1 16000 1048576 50 2000 2100 0
+\t       WRITE: 50 50 0 1048576 8000 10 1500 1600 2
+
+device proc mounted on /proc with fstype proc
+device srv2:/data mounted on /mnt/udp with fstype nfs statvers=1.1
+\topts:\tro,vers=3,proto=udp
+\txprt:\tudp 0 1 300 299 3 1200 0 4 0 0
+";
+        let stats = parse_nfs_mountstats(content.as_bytes()).unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let tcp = &stats[0];
+        assert_eq!(tcp.server, "server");
+        assert_eq!(tcp.export, "/export dir");
+        assert_eq!(tcp.mount_point, "/mnt/nfs");
+        assert_eq!(tcp.fstype, "nfs4");
+        assert_eq!(tcp.version, "4.2");
+        assert_eq!(tcp.age, 3600);
+        assert_eq!(tcp.bytes.normal_read, 1000);
+        assert_eq!(tcp.bytes.normal_write, 2000);
+        assert_eq!(tcp.bytes.direct_read, 300);
+        assert_eq!(tcp.bytes.direct_write, 400);
+        assert_eq!(tcp.bytes.server_read, 5000);
+        assert_eq!(tcp.bytes.server_write, 6000);
+        assert_eq!(tcp.bytes.read_pages, 7);
+        assert_eq!(tcp.bytes.write_pages, 8);
+        assert_eq!(tcp.transport.protocol, "tcp");
+        assert_eq!(
+            (
+                tcp.transport.sends,
+                tcp.transport.recvs,
+                tcp.transport.bad_xids
+            ),
+            (5000, 4998, 2)
+        );
+        // Only the per-op lines, not caps/sec/events/RPC iostats
+        let names: Vec<&str> = tcp.operations.iter().map(|op| op.name.as_str()).collect();
+        assert_eq!(names, ["NULL", "READ", "WRITE"]);
+        let write = &tcp.operations[2];
+        assert_eq!(
+            (write.ops, write.transmissions, write.major_timeouts),
+            (50, 50, 0)
+        );
+        assert_eq!((write.bytes_sent, write.bytes_recv), (1048576, 8000));
+        assert_eq!(
+            (write.queue_time, write.rtt, write.execute_time),
+            (10, 1500, 1600)
+        );
+        assert_eq!(write.errors, 2);
+
+        let udp = &stats[1];
+        assert_eq!(udp.fstype, "nfs");
+        assert_eq!(udp.version, "3");
+        assert_eq!(udp.transport.protocol, "udp");
+        assert_eq!(
+            (
+                udp.transport.sends,
+                udp.transport.recvs,
+                udp.transport.bad_xids
+            ),
+            (300, 299, 3)
+        );
+        assert!(udp.operations.is_empty());
+    }
+
+    #[test]
+    fn test_nfs_latency_since() {
+        let op = |ops, transmissions, rtt| NfsOpStats {
+            name: "READ".to_owned(),
+            ops,
+            transmissions,
+            rtt,
+            ..Default::default()
+        };
+        let prev = NfsMountStats {
+            operations: vec![op(10, 10, 100)],
+            ..Default::default()
+        };
+        let curr = NfsMountStats {
+            operations: vec![op(20, 22, 300)],
+            ..Default::default()
+        };
+
+        let latency = curr.latency_since(&prev);
+        assert_eq!(latency.len(), 1);
+        assert_eq!(latency[0].ops, 10);
+        assert_eq!(latency[0].retransmissions, 2);
+        assert_eq!(latency[0].avg_rtt, 20.0);
+        assert!(curr.latency_since(&curr).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_filesystem_health() {
+        let health = filesystem_health("/").unwrap();
+        assert_eq!(health.mount_point, "/");
+        if health.fstype == "ext4" {
+            assert!(health.ext4.is_some());
+        }
+
+        assert!(filesystem_health("/does/not/exist").is_err());
+
+        // ext4 remounted read-only on errors: rw per-mount, ro in the superblock
+        let mount = Mount {
+            mount_point: "/data".to_owned(),
+            fstype: "ext4".to_owned(),
+            source: "/dev/does-not-exist".to_owned(),
+            mount_options: vec!["rw".to_owned(), "relatime".to_owned()],
+            super_options: vec!["ro".to_owned(), "errors=remount-ro".to_owned()],
+            ..Default::default()
+        };
+        let health = get_filesystem_health(&mount);
+        assert!(health.read_only);
+        assert!(health.remounted_read_only);
+        assert!(health.has_errors());
+        assert_eq!(health.errors_behavior.as_deref(), Some("remount-ro"));
+
+        // Mounted read-only on purpose
+        let mount = Mount {
+            mount_options: vec!["ro".to_owned()],
+            super_options: vec!["ro".to_owned()],
+            ..mount
+        };
+        let health = get_filesystem_health(&mount);
+        assert!(health.read_only);
+        assert!(!health.has_errors());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_reconcile_fstab() {
+        if std::path::Path::new("/etc/fstab").exists() {
+            let report = reconcile_fstab().unwrap();
+
+            for entry in report.not_mounted {
+                assert!(entry.is_automount());
+            }
+            for mismatch in report.mismatches {
+                assert_eq!(mismatch.entry.mount_point, mismatch.mount.mount_point);
+            }
+        }
+
+        let options = |options: &str| options.split(',').map(String::from).collect();
+        let entry = |fstype: &str, opts: &str| FstabEntry {
+            spec: "server:/export".to_owned(),
+            mount_point: "/mnt".to_owned(),
+            fstype: fstype.to_owned(),
+            options: options(opts),
+            ..Default::default()
+        };
+        let mount = |fstype: &str, mount_options: &str, super_options: &str| Mount {
+            mount_point: "/mnt".to_owned(),
+            fstype: fstype.to_owned(),
+            mount_options: options(mount_options),
+            super_options: options(super_options),
+            ..Default::default()
+        };
+
+        // Default flags and rewritten options are not reported as missing
+        let nfs = mount("nfs4", "rw,noexec,relatime", "rw,vers=4.2,hard");
+        assert!(
+            compare_fstab_entry(&entry("nfs4", "defaults,exec,suid,nfsvers=4,hard"), &nfs)
+                .is_none()
+        );
+        let mismatch = compare_fstab_entry(&entry("nfs4", "nfsvers=3,soft"), &nfs).unwrap();
+        assert_eq!(mismatch.missing_options, vec!["nfsvers=3", "soft"]);
+
+        let vfat = mount("vfat", "rw,relatime", "rw,fmask=0022,dmask=0022");
+        assert!(compare_fstab_entry(&entry("vfat", "umask=022,async,atime"), &vfat).is_none());
+        assert!(compare_fstab_entry(&entry("vfat", "umask=077"), &vfat).is_some());
+
+        // Read-only superblock under a rw mount
+        let remounted = mount("ext4", "rw,relatime", "ro,errors=remount-ro");
+        let mismatch = compare_fstab_entry(&entry("ext4", "defaults"), &remounted).unwrap();
+        assert!(mismatch.read_only_mismatch);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_loop_devices() {
+        for device in get_loop_devices().unwrap() {
+            assert!(device.name.starts_with("loop"));
+            assert!(!device.backing_file.is_empty());
+        }
+
+        for ioblock in get_ioblocks().unwrap() {
+            // Attached loop devices, and their partitions, have a loop/ directory on the disk
+            let sysfs = std::path::Path::new("/sys/class/block").join(&ioblock.device_name);
+            let is_loop = sysfs.join("loop").exists()
+                || (sysfs.join("partition").exists() && sysfs.join("../loop").exists());
+            assert_eq!(ioblock.is_loop, is_loop);
+        }
+    }
+
+    #[test]
+    fn test_disk_forecaster() {
+        let gb = 1024 * 1024 * 1024;
+        let start = std::time::SystemTime::now();
+        let sample = |available: u64| Disks {
+            mount_point: "/data".to_owned(),
+            usage: DiskUsage {
+                available,
+                available_inodes: 1000,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        for method in [RegressionMethod::Linear, RegressionMethod::Robust] {
+            let mut forecaster = DiskForecaster::new(Duration::from_secs(24 * 3600)).method(method);
+            assert!(forecaster.forecast("/data").is_none());
+
+            // Lose 1GB per hour, starting with 10GB available
+            for hour in 0..6 {
+                let time = start + Duration::from_secs(hour * 3600);
+                forecaster.add_samples(time, &[sample((10 - hour) * gb)]);
+            }
+
+            let forecast = forecaster.forecast("/data").unwrap();
+            assert_eq!(forecast.samples, 6);
+            assert!((forecast.growth_rate - gb as f64).abs() < 1.0);
+            assert!((forecast.time_to_full.unwrap() - 5.0 * 3600.0).abs() < 1.0);
+            assert!(forecast.confidence > 0.99);
+            assert_eq!(forecast.time_to_inode_exhaustion, None);
+        }
+
+        // Samples out of the window are dropped
+        let mut forecaster = DiskForecaster::new(Duration::from_secs(3600));
+        for hour in 0..6 {
+            let time = start + Duration::from_secs(hour * 3600);
+            forecaster.add_samples(time, &[sample(gb)]);
+        }
+        assert_eq!(forecaster.forecast("/data").unwrap().samples, 2);
+
+        // A sample every 10s over a day is thinned out, but still covers the window
+        let mut forecaster = DiskForecaster::new(Duration::from_secs(24 * 3600));
+        for step in 0..8640 {
+            let time = start + Duration::from_secs(step * 10);
+            forecaster.add_samples(time, &[sample(10 * gb - step * 1024 * 1024)]);
+        }
+        let forecast = forecaster.forecast("/data").unwrap();
+        assert!(forecast.samples <= 512);
+        assert!((forecast.growth_rate - 360.0 * 1024.0 * 1024.0).abs() < 1.0);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_mount_read_only() {
+        let mount = |mount_options: &str, super_options: &str| Mount {
+            mount_options: mount_options.split(',').map(String::from).collect(),
+            super_options: super_options.split(',').map(String::from).collect(),
+            ..Default::default()
+        };
+
+        assert!(!mount("rw,relatime", "rw,errors=remount-ro").is_read_only());
+        assert!(mount("ro,relatime", "rw").is_read_only());
+        // ext4 remounted read-only on errors only flips the superblock
+        assert!(mount("rw,relatime", "ro,errors=remount-ro").is_read_only());
+
+        let mounts = get_mounts().unwrap();
+        let writable = PartitionQuery::new()
+            .exclude_read_only()
+            .skip_usage()
+            .run()
+            .unwrap();
+        for disk in writable {
+            assert!(mounts
+                .iter()
+                .any(|m| m.mount_point == disk.mount_point && !m.is_read_only()));
+        }
     }
 }
@@ -1,5 +1,9 @@
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(
+    unused_comparisons,
+    clippy::absurd_extreme_comparisons,
+    clippy::assertions_on_constants
+)]
 mod host {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use sys_metrics::host::*;
@@ -18,19 +22,19 @@ mod host {
         assert!(f >= 0.0);
 
         let b = host.system;
-        assert!(b.len() > 0);
+        assert!(!b.is_empty());
 
         let c = host.uptime;
         assert!(c > 0);
 
         let x = host.os_version;
-        assert!(x.len() > 0);
+        assert!(!x.is_empty());
 
         let y = host.kernel_version;
-        assert!(y.len() > 0);
+        assert!(!y.is_empty());
 
         let z = host.hostname;
-        assert!(z.len() > 0);
+        assert!(!z.is_empty());
     }
 
     #[test]
@@ -51,7 +55,7 @@ mod host {
         let users = get_users().unwrap();
 
         #[cfg(target_os = "linux")]
-        assert!(users.len() > 0);
+        assert!(!users.is_empty());
         #[cfg(target_os = "macos")]
         #[allow(unused_comparisons)]
         assert!(users.len() >= 0);
@@ -63,7 +67,7 @@ mod host {
 
         if x != Virtualization::Wsl {
             let uuid = get_uuid().unwrap();
-            assert!(uuid.len() > 0);
+            assert!(!uuid.is_empty());
         } else {
             // On WSL assume this test as success
             assert!(true);
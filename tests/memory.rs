@@ -1,5 +1,9 @@
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(
+    unused_comparisons,
+    clippy::absurd_extreme_comparisons,
+    clippy::assertions_on_constants
+)]
 mod memory {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use sys_metrics::memory::*;
@@ -1,5 +1,5 @@
 #[cfg(test)]
-#[allow(unused_comparisons)]
+#[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
 mod network {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use sys_metrics::network::*;
@@ -13,4 +13,297 @@ mod network {
 
         assert!(!ionets.is_empty());
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_ionets_counters() {
+        let ionets = get_ionets().unwrap();
+        let lo = ionets.iter().find(|io| io.interface == "lo").unwrap();
+
+        // The loopback has no physical layer to fail
+        assert_eq!(lo.rx_frame, 0);
+        assert_eq!(lo.tx_carrier, 0);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_interfaces() {
+        let interfaces = get_interfaces().unwrap();
+
+        let lo = interfaces.iter().find(|i| i.name == "lo").unwrap();
+        assert_eq!(lo.kind, InterfaceKind::Loopback);
+        assert!(!lo.is_physical);
+        for interface in interfaces {
+            assert!(interface.ifindex > 0);
+        }
+    }
+
+    #[test]
+    fn test_interface_addresses() {
+        let interfaces = get_interface_addresses().unwrap();
+
+        let lo = interfaces.iter().find(|i| i.is_loopback).unwrap();
+        assert!(lo.is_up);
+        for ipv4 in &lo.ipv4 {
+            assert!(ipv4.address.is_loopback());
+            assert_eq!(ipv4.prefix_len, 8);
+        }
+        for ipv6 in &lo.ipv6 {
+            // macOS also has fe80::1 on lo0
+            if ipv6.address.is_loopback() {
+                assert_eq!(ipv6.scope, Ipv6Scope::Host);
+            } else {
+                assert_eq!(ipv6.scope, Ipv6Scope::Link);
+                assert_eq!(ipv6.address.segments()[1], 0);
+            }
+        }
+        for ipv6 in interfaces.iter().flat_map(|i| &i.ipv6) {
+            if ipv6.address.segments()[0] & 0xffc0 == 0xfe80 {
+                assert_eq!(ipv6.scope, Ipv6Scope::Link);
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_sockets() {
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let tcp = get_tcp_sockets().unwrap();
+        let socket = tcp.iter().find(|s| s.local_port == port).unwrap();
+        assert_eq!(socket.state, TcpState::Listen);
+        assert_eq!(socket.local_address.to_string(), "127.0.0.1");
+        assert!(socket.drops.is_none());
+
+        let summary = TcpStateSummary::from_sockets(&tcp);
+        assert!(summary.listen >= 1);
+        assert_eq!(summary.total, tcp.len());
+
+        for socket in get_udp_sockets().unwrap() {
+            assert!(socket.drops.is_some());
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_protocol_stats() {
+        let stats = get_protocol_stats().unwrap();
+
+        assert!(stats.ip.in_delivers <= stats.ip.in_receives);
+        assert_eq!(stats.tcp.in_segs as i64, stats.sections["Tcp"]["InSegs"]);
+        assert!(stats.tcp_ext.listen_drops >= stats.tcp_ext.listen_overflows);
+        assert!(stats.sections["Ip"].contains_key("InReceives"));
+        assert!(stats.sections["TcpExt"].contains_key("ListenOverflows"));
+        // MaxConn is -1 (dynamic)
+        assert_eq!(stats.sections["Tcp"].get("MaxConn"), Some(&-1));
+        // The IPv6 counters are keyed by their own prefix
+        if let Some(icmp6) = stats.sections.get("Icmp6") {
+            assert!(icmp6.contains_key("InMsgs"));
+            assert!(!stats.sections["Ip6"].contains_key("Icmp6InMsgs"));
+        }
+    }
+
+    #[test]
+    fn test_ionet_sampler() {
+        use std::time::{Duration, Instant};
+
+        let ionet = |rx_bytes: u64| IoNet {
+            interface: "sampler-test0".to_owned(),
+            rx_bytes,
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let mut sampler = IoNetSampler::new();
+
+        assert!(sampler.sample_at(start, &[ionet(1000)]).is_empty());
+
+        let rates = sampler.sample_at(start + Duration::from_secs(2), &[ionet(3000)]);
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].rx_bytes, 1000.0);
+        assert!(!rates[0].reset);
+
+        // 32 bits counter wrap
+        let before_wrap = u32::MAX as u64 - 99;
+        sampler.sample_at(start + Duration::from_secs(3), &[ionet(before_wrap)]);
+        let rates = sampler.sample_at(start + Duration::from_secs(4), &[ionet(100)]);
+        assert_eq!(rates[0].rx_bytes, 200.0);
+        assert!(!rates[0].reset);
+
+        // Counters reset
+        let rates = sampler.sample_at(start + Duration::from_secs(5), &[ionet(10)]);
+        assert!(rates[0].reset);
+        assert_eq!(rates[0].rx_bytes, 0.0);
+        let rates = sampler.sample_at(start + Duration::from_secs(6), &[ionet(20)]);
+        assert_eq!(rates[0].rx_bytes, 10.0);
+
+        // A counter reset from ~3GB is not mistaken for a 32 bits wrap
+        let mut sampler = IoNetSampler::new();
+        sampler.sample_at(start, &[ionet(2_900_000_000)]);
+        sampler.sample_at(start + Duration::from_secs(1), &[ionet(3_000_000_000)]);
+        let rates = sampler.sample_at(start + Duration::from_secs(2), &[ionet(1000)]);
+        assert!(rates[0].reset);
+        assert_eq!(rates[0].rx_bytes, 0.0);
+
+        // Counters which went above u32::MAX never wrap at 32 bits
+        let mut sampler = IoNetSampler::new();
+        sampler.sample_at(start, &[ionet(u32::MAX as u64 + 10)]);
+        sampler.sample_at(
+            start + Duration::from_secs(1),
+            &[ionet(u32::MAX as u64 - 10)],
+        );
+        let rates = sampler.sample_at(start + Duration::from_secs(2), &[ionet(10)]);
+        assert!(rates[0].reset);
+
+        let ionets = get_ionets().unwrap();
+        sampler.sample(&ionets);
+        for rate in sampler.sample(&ionets) {
+            assert!(rate.rx_bytes >= 0.0);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_routes() {
+        let routes = get_routes().unwrap();
+
+        for route in &routes {
+            assert!(!route.interface.is_empty());
+            // RTF_LOCAL routes are left out
+            assert_eq!(route.flags & 0x8000_0000, 0);
+            match route.destination {
+                std::net::IpAddr::V4(_) => assert!(route.prefix_len <= 32),
+                std::net::IpAddr::V6(_) => assert!(route.prefix_len <= 128),
+            }
+        }
+
+        let gateway = get_default_gateway().unwrap();
+        for route in gateway.ipv4.iter().chain(gateway.ipv6.iter()) {
+            assert!(route.is_default());
+            assert!(route.is_up());
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_neighbors() {
+        for neighbor in get_neighbors().unwrap() {
+            assert!(!neighbor.interface.is_empty());
+            if neighbor.is_permanent() {
+                assert!(neighbor.is_complete());
+            }
+        }
+
+        let tables = get_neighbor_tables().unwrap();
+        assert!(tables.ipv4.gc_thresh1 <= tables.ipv4.gc_thresh3);
+        assert!(tables.ipv4.usage() >= 0.0);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_socket_summary() {
+        let summary = get_socket_summary().unwrap();
+
+        assert!(summary.sockets_used > 0);
+        assert!(summary.tcp_mem.min <= summary.tcp_mem.pressure);
+        assert!(summary.tcp_mem.pressure <= summary.tcp_mem.max);
+        assert!(summary.tcp_mem_usage() >= 0.0);
+        if let Some(conntrack) = summary.conntrack {
+            assert!(conntrack.count <= conntrack.max);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_softnet_stats() {
+        let previous = get_softnet_stats().unwrap();
+        assert!(!previous.is_empty());
+
+        let current = get_softnet_stats().unwrap();
+        let deltas = SoftnetStat::deltas(&previous, &current);
+        assert_eq!(deltas.len(), current.len());
+
+        // 32 bits counter wrap
+        let prev = SoftnetStat {
+            dropped: u32::MAX as u64,
+            ..Default::default()
+        };
+        let curr = SoftnetStat {
+            dropped: 4,
+            ..Default::default()
+        };
+        assert_eq!(curr.delta(&prev).dropped, 5);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_wireless_stats() {
+        for stats in get_wireless_stats().unwrap() {
+            assert!(is_wireless_interface(&stats.interface));
+        }
+        assert!(!is_wireless_interface("lo"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_wireless_stats() {
+        let content = "\
+Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+ wlan0: 0000   54.  -56.  -256        0      0      0      3      7        2
+wlp3s0: 001a   70   -40.   -92.       1      2      3      4      5        6
+";
+        let stats = parse_wireless_stats(content.as_bytes()).unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let wlan0 = &stats[0];
+        assert_eq!(wlan0.interface, "wlan0");
+        assert_eq!(wlan0.status, 0);
+        assert_eq!(wlan0.link_quality, 54.0);
+        assert_eq!(wlan0.signal_level, -56.0);
+        // No noise measurement
+        assert_eq!(wlan0.noise_level, None);
+        assert_eq!(wlan0.discarded_retry, 3);
+        assert_eq!(wlan0.discarded_misc, 7);
+        assert_eq!(wlan0.missed_beacon, 2);
+
+        let wlp3s0 = &stats[1];
+        assert_eq!(wlp3s0.status, 0x1a);
+        assert_eq!(wlp3s0.link_quality, 70.0);
+        assert_eq!(wlp3s0.noise_level, Some(-92.0));
+        assert_eq!(
+            (
+                wlp3s0.discarded_nwid,
+                wlp3s0.discarded_crypt,
+                wlp3s0.discarded_frag
+            ),
+            (1, 2, 3)
+        );
+
+        let invalid = "header\nheader\nwlan0: 0000 54. -56.\n";
+        assert!(parse_wireless_stats(invalid.as_bytes()).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_listening_ports() {
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let ports = get_listening_ports(true).unwrap();
+        let ours = ports
+            .iter()
+            .find(|p| p.protocol == SocketProtocol::Tcp && p.port == port)
+            .unwrap();
+        assert_eq!(ours.pid, Some(std::process::id()));
+        assert!(ours.process_name.is_some());
+
+        for port in ports.iter().filter(|p| p.protocol == SocketProtocol::Unix) {
+            assert!(port.address.is_none());
+        }
+    }
 }